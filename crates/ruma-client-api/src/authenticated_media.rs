@@ -2,8 +2,122 @@
 //!
 //! [MSC3916]: https://github.com/matrix-org/matrix-spec-proposals/pull/3916
 
+use ruma_common::{
+    api::{KnownFeature, MatrixVersion},
+    IdParseError,
+};
+use ruma_events::room::MediaSource;
+
 pub mod get_content;
 pub mod get_content_as_filename;
 pub mod get_content_thumbnail;
 pub mod get_media_config;
 pub mod get_media_preview;
+
+/// Whether the authenticated media endpoints should be used rather than the legacy media
+/// endpoints, given the Matrix versions supported by the server.
+///
+/// Authenticated media was added in Matrix 1.11, so this returns `true` if any of the versions is
+/// 1.11 or later.
+pub fn prefers_authenticated_media(versions: &[MatrixVersion]) -> bool {
+    let required = MatrixVersion::required_for(KnownFeature::AuthenticatedMedia);
+    versions.iter().any(|version| version.is_superset_of(required))
+}
+
+/// A request to download the content of a media file, with either the authenticated or the legacy
+/// media endpoint.
+#[derive(Clone, Debug)]
+#[allow(clippy::exhaustive_enums)]
+pub enum MediaDownloadRequest {
+    /// A request to the authenticated media endpoint.
+    Authenticated(get_content::unstable::Request),
+
+    /// A request to the legacy media endpoint.
+    Legacy(crate::media::get_content::v3::Request),
+}
+
+impl MediaDownloadRequest {
+    /// Creates a new `MediaDownloadRequest` for the MXC URI of the given media source.
+    ///
+    /// If `authenticated` is `true`, the authenticated media endpoint is used, otherwise the legacy
+    /// media endpoint is used. Use [`prefers_authenticated_media()`] to decide which one to use
+    /// given the versions supported by the server.
+    ///
+    /// Returns an error if the MXC URI of the media source is invalid.
+    pub fn from_source(source: &MediaSource, authenticated: bool) -> Result<Self, IdParseError> {
+        let uri = source.mxc_uri();
+
+        Ok(if authenticated {
+            Self::Authenticated(get_content::unstable::Request::from_uri(uri)?)
+        } else {
+            Self::Legacy(crate::media::get_content::v3::Request::from_url(uri)?)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches2::assert_matches;
+    use ruma_common::{api::MatrixVersion, mxc_uri, owned_mxc_uri, serde::Base64};
+    use ruma_events::room::{EncryptedFileInit, JsonWebKeyInit, MediaSource};
+
+    use super::{prefers_authenticated_media, MediaDownloadRequest};
+
+    #[test]
+    fn authenticated_media_cutover() {
+        assert!(!prefers_authenticated_media(&[]));
+        assert!(!prefers_authenticated_media(&[MatrixVersion::V1_0, MatrixVersion::V1_10]));
+        assert!(prefers_authenticated_media(&[MatrixVersion::V1_10, MatrixVersion::V1_11]));
+    }
+
+    #[test]
+    fn download_request_plain_source() {
+        let source = MediaSource::Plain(owned_mxc_uri!("mxc://notareal.hs/abcdef"));
+
+        assert_matches!(
+            MediaDownloadRequest::from_source(&source, true),
+            Ok(MediaDownloadRequest::Authenticated(request))
+        );
+        assert_eq!(request.server_name, "notareal.hs");
+        assert_eq!(request.media_id, "abcdef");
+
+        assert_matches!(
+            MediaDownloadRequest::from_source(&source, false),
+            Ok(MediaDownloadRequest::Legacy(request))
+        );
+        assert_eq!(request.server_name, "notareal.hs");
+        assert_eq!(request.media_id, "abcdef");
+    }
+
+    #[test]
+    fn download_request_encrypted_source() {
+        let source = MediaSource::Encrypted(Box::new(
+            EncryptedFileInit {
+                url: mxc_uri!("mxc://notareal.hs/encrypted").to_owned(),
+                key: JsonWebKeyInit {
+                    kty: "oct".to_owned(),
+                    key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+                    alg: "A256CTR".to_owned(),
+                    k: Base64::parse("TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A").unwrap(),
+                    ext: true,
+                }
+                .into(),
+                iv: Base64::parse("S22dq3NAX8wAAAAAAAAAAA").unwrap(),
+                hashes: [(
+                    "sha256".to_owned(),
+                    Base64::parse("aWOHudBnDkJ9IwaR1Nd8XKoI7DOrqDTwt6xDPfVGN6Q").unwrap(),
+                )]
+                .into(),
+                v: "v2".to_owned(),
+            }
+            .into(),
+        ));
+
+        assert_matches!(
+            MediaDownloadRequest::from_source(&source, true),
+            Ok(MediaDownloadRequest::Authenticated(request))
+        );
+        assert_eq!(request.server_name, "notareal.hs");
+        assert_eq!(request.media_id, "encrypted");
+    }
+}
@@ -114,4 +114,37 @@ pub mod v3 {
             Self { event_id }
         }
     }
+
+    #[cfg(all(test, feature = "client"))]
+    mod tests {
+        use js_int::UInt;
+        use ruma_common::{
+            api::{MatrixVersion, OutgoingRequest as _, SendAccessToken},
+            owned_room_id, MilliSecondsSinceUnixEpoch,
+        };
+        use ruma_events::room::message::RoomMessageEventContent;
+
+        use super::Request;
+
+        #[test]
+        fn serialize_with_timestamp() {
+            let mut req = Request::new(
+                owned_room_id!("!room:server.tld"),
+                "txn".into(),
+                &RoomMessageEventContent::text_plain("Hello"),
+            )
+            .unwrap();
+            req.timestamp = Some(MilliSecondsSinceUnixEpoch(UInt::new_wrapping(1_700_000_000_000)));
+
+            let http_req = req
+                .try_into_http_request::<Vec<u8>>(
+                    "https://server.tld",
+                    SendAccessToken::Appservice("as_token"),
+                    &[MatrixVersion::V1_1],
+                )
+                .unwrap();
+
+            assert_eq!(http_req.uri().query(), Some("ts=1700000000000"));
+        }
+    }
 }
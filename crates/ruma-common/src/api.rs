@@ -31,6 +31,8 @@ use crate::UserId;
 ///     method: GET, // one of the associated constants of http::Method
 ///     rate_limited: true,
 ///     authentication: AccessToken, // one of the variants of api::AuthScheme
+///     // optional, one of the variants of api::BodyFormat, defaults to `Json`
+///     body_format: FormUrlEncoded,
 ///
 ///     // history of endpoint paths
 ///     // there must be at least one path but otherwise everything is optional
@@ -47,8 +49,31 @@ use crate::UserId;
 #[macro_export]
 macro_rules! metadata {
     ( $( $field:ident: $rhs:tt ),+ $(,)? ) => {
+        $crate::metadata!(@struct [] [] $( $field: $rhs, )+)
+    };
+
+    // The body format is optional, so it is collected separately from the other fields
+    ( @struct [ $($fields:tt)* ] [ $($body_format:tt)* ] body_format: $rhs:ident, $($rest:tt)* ) => {
+        $crate::metadata!(@struct [ $($fields)* ] [ $rhs ] $($rest)*)
+    };
+
+    ( @struct [ $($fields:tt)* ] [ $($body_format:tt)* ] $field:ident: $rhs:tt, $($rest:tt)* ) => {
+        $crate::metadata!(
+            @struct
+            [ $($fields)* $field: $crate::metadata!(@field $field: $rhs), ]
+            [ $($body_format)* ]
+            $($rest)*
+        )
+    };
+
+    ( @struct [ $($fields:tt)* ] [] ) => {
+        $crate::metadata!(@struct [ $($fields)* ] [ Json ])
+    };
+
+    ( @struct [ $($fields:tt)* ] [ $body_format:ident ] ) => {
         $crate::api::Metadata {
-            $( $field: $crate::metadata!(@field $field: $rhs) ),+
+            $($fields)*
+            body_format: $crate::api::BodyFormat::$body_format,
         }
     };
 
@@ -309,7 +334,10 @@ pub use ruma_macros::response;
 pub mod error;
 mod metadata;
 
-pub use self::metadata::{MatrixVersion, Metadata, VersionHistory, VersioningDecision};
+pub use self::metadata::{
+    EndpointDescription, KnownFeature, MatrixVersion, Metadata, PathArgument, PreEncoded,
+    VersionHistory, VersioningDecision,
+};
 
 /// An enum to control whether an access token should be added to outgoing requests
 #[derive(Clone, Copy, Debug)]
@@ -354,6 +382,18 @@ impl<'a> SendAccessToken<'a> {
     pub fn get_required_for_appservice(self) -> Option<&'a str> {
         as_variant!(self, Self::Appservice | Self::Always)
     }
+
+    /// Whether `self` contains an access token, regardless of when it should be sent.
+    pub fn is_some(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+
+    /// Get the access token contained in `self`, regardless of when it should be sent.
+    ///
+    /// Returns `None` only if `self` is `SendAccessToken::None`.
+    pub fn as_str(&self) -> Option<&'a str> {
+        as_variant!(*self, Self::IfRequired | Self::Appservice | Self::Always)
+    }
 }
 
 /// A request type for a Matrix API endpoint, used for sending requests.
@@ -379,6 +419,9 @@ pub trait OutgoingRequest: Sized + Clone {
     /// - The endpoint is too new, and no unstable path is known for this endpoint.
     ///   ([`NoUnstablePath`](error::IntoHttpError::NoUnstablePath))
     ///
+    /// If `considering_versions` is empty and no unstable path is known for this endpoint, it
+    /// fails with [`NoSupportedVersion`](error::IntoHttpError::NoSupportedVersion).
+    ///
     /// Finally, this will emit a warning through `tracing` if it detects if any version in
     /// `considering_versions` has deprecated this endpoint.
     ///
@@ -439,6 +482,35 @@ pub trait OutgoingRequestAppserviceExt: OutgoingRequest {
 
         Ok(http_request)
     }
+
+    /// Tries to convert this request into an `http::Request` and, if the endpoint uses the
+    /// [`AuthScheme::AppserviceToken`] authentication scheme, appends a virtual `user_id` to
+    /// [assert Appservice identity][id_assert].
+    ///
+    /// For endpoints using another authentication scheme, this is the same as
+    /// [`try_into_http_request()`](OutgoingRequest::try_into_http_request). Use
+    /// [`try_into_http_request_with_user_id()`](Self::try_into_http_request_with_user_id) to
+    /// always append the `user_id`.
+    ///
+    /// [id_assert]: https://spec.matrix.org/latest/application-service-api/#identity-assertion
+    fn try_into_appservice_http_request<T: Default + BufMut>(
+        self,
+        base_url: &str,
+        access_token: SendAccessToken<'_>,
+        user_id: &UserId,
+        considering_versions: &'_ [MatrixVersion],
+    ) -> Result<http::Request<T>, IntoHttpError> {
+        if Self::METADATA.authentication == AuthScheme::AppserviceToken {
+            self.try_into_http_request_with_user_id(
+                base_url,
+                access_token,
+                user_id,
+                considering_versions,
+            )
+        } else {
+            self.try_into_http_request(base_url, access_token, considering_versions)
+        }
+    }
 }
 
 impl<T: OutgoingRequest> OutgoingRequestAppserviceExt for T {}
@@ -488,7 +560,7 @@ pub trait EndpointError: OutgoingResponse + StdError + Sized + Send + 'static {
 }
 
 /// Authentication scheme used by the endpoint.
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[allow(clippy::exhaustive_enums)]
 pub enum AuthScheme {
     /// No authentication is performed.
@@ -517,6 +589,42 @@ pub enum AuthScheme {
     ServerSignatures,
 }
 
+/// The kind of token that can be used to authenticate a request.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[allow(clippy::exhaustive_enums)]
+pub enum TokenKind {
+    /// The access token of a user, corresponding to [`SendAccessToken::IfRequired`].
+    AccessToken,
+
+    /// The token of an application service, corresponding to [`SendAccessToken::Appservice`].
+    AppserviceToken,
+}
+
+/// The format of the body of requests to an endpoint.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[allow(clippy::exhaustive_enums)]
+pub enum BodyFormat {
+    /// The body is a JSON object.
+    #[default]
+    Json,
+
+    /// The body is form-encoded, like a query string.
+    ///
+    /// This is only used for interoperability with legacy servers, the Matrix specification
+    /// always uses JSON request bodies.
+    FormUrlEncoded,
+}
+
+impl BodyFormat {
+    /// The value of the `Content-Type` header for a request body in this format.
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::FormUrlEncoded => "application/x-www-form-urlencoded",
+        }
+    }
+}
+
 /// The direction to return events from.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[allow(clippy::exhaustive_enums)]
@@ -530,3 +638,27 @@ pub enum Direction {
     #[serde(rename = "f")]
     Forward,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SendAccessToken;
+
+    #[test]
+    fn send_access_token_accessors() {
+        let token = SendAccessToken::IfRequired("if_required");
+        assert!(token.is_some());
+        assert_eq!(token.as_str(), Some("if_required"));
+
+        let token = SendAccessToken::Always("always");
+        assert!(token.is_some());
+        assert_eq!(token.as_str(), Some("always"));
+
+        let token = SendAccessToken::Appservice("appservice");
+        assert!(token.is_some());
+        assert_eq!(token.as_str(), Some("appservice"));
+
+        let token = SendAccessToken::None;
+        assert!(!token.is_some());
+        assert_eq!(token.as_str(), None);
+    }
+}
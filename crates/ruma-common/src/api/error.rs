@@ -112,6 +112,21 @@ pub enum IntoHttpError {
     )]
     NoUnstablePath,
 
+    /// Tried to create a request without any [`MatrixVersion`], for an endpoint that has no
+    /// unstable path.
+    ///
+    /// The supported versions of the server should be queried first, for example with the
+    /// `/versions` endpoint of the Client-Server API.
+    #[error(
+        "no Matrix versions were given, and the endpoint has no unstable path; \
+         query the versions supported by the server first"
+    )]
+    NoSupportedVersion,
+
+    /// Tried to create a request with a base URL that is not an absolute `http` or `https` URL.
+    #[error("the base URL is not an absolute http(s) URL")]
+    InvalidBaseUrl,
+
     /// Tried to create a request with [`MatrixVersion`]s for all of which this endpoint was
     /// removed.
     #[error("could not create any path variant for endpoint, as it was removed in version {0}")]
@@ -305,6 +320,84 @@ impl fmt::Display for UnknownVersionError {
 
 impl StdError for UnknownVersionError {}
 
+/// An error that happens when the invariants of a [`VersionHistory`] are not respected.
+///
+/// [`VersionHistory`]: super::VersionHistory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VersionHistoryError {
+    /// No paths were supplied.
+    NoPaths,
+
+    /// A path contains non-ASCII or whitespace characters.
+    InvalidPath,
+
+    /// The names of the path arguments are not the same in all paths.
+    PathArgumentsMismatch,
+
+    /// The number of path arguments is not the same in all paths.
+    PathArgumentsCountMismatch,
+
+    /// A Matrix version is used for several stable paths.
+    DuplicateStableVersion,
+
+    /// The stable paths are not in ascending order of Matrix version.
+    UnorderedStableVersions,
+
+    /// The deprecated version is equal to the version of the latest stable path.
+    DeprecatedEqualToStable,
+
+    /// The deprecated version is older than the version of the latest stable path.
+    DeprecatedOlderThanStable,
+
+    /// A deprecated version is defined without any stable path.
+    DeprecatedWithoutStable,
+
+    /// The removed version is equal to the deprecated version.
+    RemovedEqualToDeprecated,
+
+    /// The removed version is older than the deprecated version.
+    RemovedOlderThanDeprecated,
+
+    /// A removed version is defined without a deprecated version.
+    RemovedWithoutDeprecated,
+}
+
+impl VersionHistoryError {
+    pub(super) const fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoPaths => "No paths supplied",
+            Self::InvalidPath => "path contains invalid (non-ascii or whitespace) characters",
+            Self::PathArgumentsMismatch => "Path Arguments do not match",
+            Self::PathArgumentsCountMismatch => "Amount of Path Arguments do not match",
+            Self::DuplicateStableVersion => "Duplicate matrix version in stable_paths",
+            Self::UnorderedStableVersions => "No ascending order in stable_paths",
+            Self::DeprecatedEqualToStable => {
+                "deprecated version is equal to latest stable path version"
+            }
+            Self::DeprecatedOlderThanStable => {
+                "deprecated version is older than latest stable path version"
+            }
+            Self::DeprecatedWithoutStable => {
+                "Defined deprecated version while no stable path exists"
+            }
+            Self::RemovedEqualToDeprecated => "removed version is equal to deprecated version",
+            Self::RemovedOlderThanDeprecated => "removed version is older than deprecated version",
+            Self::RemovedWithoutDeprecated => {
+                "Defined removed version while no deprecated version exists"
+            }
+        }
+    }
+}
+
+impl fmt::Display for VersionHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl StdError for VersionHistoryError {}
+
 /// An error that happens when an incorrect amount of arguments have been passed to PathData parts
 /// formatting.
 #[derive(Debug)]
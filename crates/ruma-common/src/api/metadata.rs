@@ -9,12 +9,14 @@ use http::{
     header::{self, HeaderName, HeaderValue},
     Method,
 };
+use indexmap::IndexMap;
 use percent_encoding::utf8_percent_encode;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use tracing::warn;
 
 use super::{
-    error::{IntoHttpError, UnknownVersionError},
-    AuthScheme, SendAccessToken,
+    error::{IntoHttpError, UnknownVersionError, VersionHistoryError},
+    AuthScheme, BodyFormat, SendAccessToken, TokenKind,
 };
 use crate::{percent_encode::PATH_PERCENT_ENCODE_SET, serde::slice_to_buf, RoomVersionId};
 
@@ -33,6 +35,9 @@ pub struct Metadata {
 
     /// All info pertaining to an endpoint's (historic) paths, deprecation version, and removal.
     pub history: VersionHistory,
+
+    /// The format of the request body of this endpoint.
+    pub body_format: BodyFormat,
 }
 
 impl Metadata {
@@ -60,35 +65,72 @@ impl Metadata {
         &self,
         access_token: SendAccessToken<'_>,
     ) -> Result<Option<(HeaderName, HeaderValue)>, IntoHttpError> {
-        Ok(match self.authentication {
-            AuthScheme::None => match access_token.get_not_required_for_endpoint() {
-                Some(token) => Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?)),
-                None => None,
-            },
+        Ok(match self.access_token_to_send(access_token)? {
+            Some(token) => Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?)),
+            None => None,
+        })
+    }
 
-            AuthScheme::AccessToken => {
-                let token = access_token
-                    .get_required_for_endpoint()
-                    .ok_or(IntoHttpError::NeedsAuthentication)?;
+    /// Transform the `SendAccessToken` into an `access_token` query parameter if the endpoint
+    /// requires it, or if it is `SendAccessToken::Always`.
+    ///
+    /// This is the deprecated way of sending the access token, that should only be used with
+    /// servers that don't support the `Authorization` header. The returned key and value are not
+    /// percent-encoded, they can be encoded with [`encode_query_value()`].
+    ///
+    /// Fails if the endpoint requires an access token but the parameter is `SendAccessToken::None`.
+    ///
+    /// [`encode_query_value()`]: crate::percent_encode::encode_query_value
+    pub fn authorization_query_param(
+        &self,
+        access_token: SendAccessToken<'_>,
+    ) -> Result<Option<(String, String)>, IntoHttpError> {
+        Ok(self
+            .access_token_to_send(access_token)?
+            .map(|token| ("access_token".to_owned(), token.to_owned())))
+    }
 
-                Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?))
+    /// The kinds of token that are accepted to authenticate requests to this endpoint, in order of
+    /// precedence.
+    ///
+    /// This is empty if the endpoint doesn't use token authentication. It doesn't take into
+    /// account tokens sent with [`SendAccessToken::Always`], which are sent regardless of the
+    /// authentication scheme.
+    pub fn accepted_tokens(&self) -> &'static [TokenKind] {
+        match self.authentication {
+            AuthScheme::None | AuthScheme::ServerSignatures => &[],
+            AuthScheme::AccessToken | AuthScheme::AccessTokenOptional => {
+                &[TokenKind::AccessToken, TokenKind::AppserviceToken]
             }
+            AuthScheme::AppserviceToken => &[TokenKind::AppserviceToken],
+        }
+    }
 
-            AuthScheme::AccessTokenOptional => match access_token.get_required_for_endpoint() {
-                Some(token) => Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?)),
-                None => None,
-            },
+    /// The access token to send for this endpoint, according to its authentication scheme.
+    fn access_token_to_send<'a>(
+        &self,
+        access_token: SendAccessToken<'a>,
+    ) -> Result<Option<&'a str>, IntoHttpError> {
+        Ok(match self.authentication {
+            AuthScheme::None => access_token.get_not_required_for_endpoint(),
 
-            AuthScheme::AppserviceToken => match access_token.get_required_for_appservice() {
-                Some(token) => Some((header::AUTHORIZATION, format!("Bearer {token}").try_into()?)),
-                None => None,
-            },
+            AuthScheme::AccessToken => Some(
+                access_token
+                    .get_required_for_endpoint()
+                    .ok_or(IntoHttpError::NeedsAuthentication)?,
+            ),
+
+            AuthScheme::AccessTokenOptional => access_token.get_required_for_endpoint(),
+
+            AuthScheme::AppserviceToken => access_token.get_required_for_appservice(),
 
             AuthScheme::ServerSignatures => None,
         })
     }
 
     /// Generate the endpoint URL for this endpoint.
+    ///
+    /// Returns an error if `base_url` is not an absolute `http` or `https` URL.
     pub fn make_endpoint_url(
         &self,
         versions: &[MatrixVersion],
@@ -96,11 +138,51 @@ impl Metadata {
         path_args: &[&dyn Display],
         query_string: &str,
     ) -> Result<String, IntoHttpError> {
+        self.make_endpoint_url_inner(
+            versions,
+            base_url,
+            path_args.iter().map(|arg| arg as &dyn PathArgument),
+            query_string,
+        )
+    }
+
+    /// Generate the endpoint URL for this endpoint, with path arguments that can be already
+    /// percent-encoded.
+    ///
+    /// Path arguments wrapped in [`PreEncoded`] are inserted in the path as-is, other arguments are
+    /// percent-encoded like with [`make_endpoint_url()`](Self::make_endpoint_url).
+    ///
+    /// Returns an error if `base_url` is not an absolute `http` or `https` URL.
+    pub fn make_endpoint_url_raw(
+        &self,
+        versions: &[MatrixVersion],
+        base_url: &str,
+        path_args: &[&dyn PathArgument],
+        query_string: &str,
+    ) -> Result<String, IntoHttpError> {
+        self.make_endpoint_url_inner(versions, base_url, path_args.iter().copied(), query_string)
+    }
+
+    fn make_endpoint_url_inner<'a>(
+        &self,
+        versions: &[MatrixVersion],
+        base_url: &str,
+        mut path_args: impl Iterator<Item = &'a dyn PathArgument>,
+        query_string: &str,
+    ) -> Result<String, IntoHttpError> {
+        let is_valid_base_url = url::Url::parse(base_url).is_ok_and(|url| {
+            matches!(url.scheme(), "http" | "https")
+                && url.has_host()
+                && !base_url.contains(char::is_whitespace)
+        });
+        if !is_valid_base_url {
+            return Err(IntoHttpError::InvalidBaseUrl);
+        }
+
         let path_with_placeholders = self.history.select_path(versions)?;
 
         let mut res = base_url.strip_suffix('/').unwrap_or(base_url).to_owned();
         let mut segments = path_with_placeholders.split('/');
-        let mut path_args = path_args.iter();
 
         let first_segment = segments.next().expect("split iterator is never empty");
         assert!(first_segment.is_empty(), "endpoint paths must start with '/'");
@@ -109,11 +191,10 @@ impl Metadata {
             if segment.starts_with(':') {
                 let arg = path_args
                     .next()
-                    .expect("number of placeholders must match number of arguments")
-                    .to_string();
-                let arg = utf8_percent_encode(&arg, PATH_PERCENT_ENCODE_SET);
+                    .expect("number of placeholders must match number of arguments");
 
-                write!(res, "/{arg}").expect("writing to a String using fmt::Write can't fail");
+                res.push('/');
+                arg.write_percent_encoded(&mut res);
             } else {
                 res.reserve(segment.len() + 1);
                 res.push('/');
@@ -129,6 +210,38 @@ impl Metadata {
         Ok(res)
     }
 
+    /// The canonical path of this endpoint, with its placeholders.
+    ///
+    /// This is the path of the latest stable version if there is one, otherwise the last unstable
+    /// path.
+    pub fn canonical_path(&self) -> &'static str {
+        self.history
+            .stable_paths
+            .last()
+            .map(|(_, path)| *path)
+            .or_else(|| self.history.unstable())
+            .expect("endpoint has at least one path")
+    }
+
+    /// Get a serializable description of this endpoint.
+    ///
+    /// This is useful for tooling, for example to generate documentation of the endpoints.
+    pub fn describe(&self) -> EndpointDescription {
+        EndpointDescription {
+            method: self.method.to_string(),
+            authentication: self.authentication,
+            rate_limited: self.rate_limited,
+            unstable_paths: self.history.unstable_paths().map(ToOwned::to_owned).collect(),
+            stable_paths: self
+                .history
+                .stable_paths()
+                .map(|(version, path)| (version.to_string(), path.to_owned()))
+                .collect(),
+            deprecated: self.history.deprecated_in().map(|version| version.to_string()),
+            removed: self.history.removed_in().map(|version| version.to_string()),
+        }
+    }
+
     // Used for generated `#[test]`s
     #[doc(hidden)]
     pub fn _path_parameters(&self) -> Vec<&'static str> {
@@ -137,6 +250,63 @@ impl Metadata {
     }
 }
 
+/// An argument of an endpoint path, for [`Metadata::make_endpoint_url_raw()`].
+///
+/// This is implemented for all types implementing `Display`, which are percent-encoded, and for
+/// [`PreEncoded`], which is inserted as-is.
+pub trait PathArgument {
+    /// Append the percent-encoded form of this argument to the given string.
+    fn write_percent_encoded(&self, out: &mut String);
+}
+
+impl<T: Display + ?Sized> PathArgument for T {
+    fn write_percent_encoded(&self, out: &mut String) {
+        let arg = self.to_string();
+        write!(out, "{}", utf8_percent_encode(&arg, PATH_PERCENT_ENCODE_SET))
+            .expect("writing to a String using fmt::Write can't fail");
+    }
+}
+
+/// A path argument that is already percent-encoded.
+///
+/// It must not contain unencoded `/`, `?` or `#` characters.
+#[derive(Clone, Copy, Debug)]
+#[allow(clippy::exhaustive_structs)]
+pub struct PreEncoded<'a>(pub &'a str);
+
+impl PathArgument for PreEncoded<'_> {
+    fn write_percent_encoded(&self, out: &mut String) {
+        out.push_str(self.0);
+    }
+}
+
+/// A serializable description of an API endpoint, as returned by [`Metadata::describe()`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[allow(clippy::exhaustive_structs)]
+pub struct EndpointDescription {
+    /// The HTTP method used by this endpoint.
+    pub method: String,
+
+    /// What authentication scheme the server uses for this endpoint.
+    pub authentication: AuthScheme,
+
+    /// Whether or not this endpoint is rate limited by the server.
+    pub rate_limited: bool,
+
+    /// The unstable paths of this endpoint, from oldest to latest.
+    pub unstable_paths: Vec<String>,
+
+    /// The stable paths of this endpoint, as a map of Matrix version to path, from oldest to
+    /// latest.
+    pub stable_paths: IndexMap<String, String>,
+
+    /// The Matrix version that deprecated this endpoint, if any.
+    pub deprecated: Option<String>,
+
+    /// The Matrix version that removed this endpoint, if any.
+    pub removed: Option<String>,
+}
+
 /// The complete history of this endpoint as far as Ruma knows, together with all variants on
 /// versions stable and unstable.
 ///
@@ -182,24 +352,49 @@ impl VersionHistory {
     ///   version 1.0, and only if any stable path is defined
     /// - removed comes after deprecated, or after the latest referenced stable_paths, like
     ///   deprecated
+    ///
+    /// Use [`VersionHistory::try_new()`] to get an error instead of a panic when the invariants
+    /// are not respected.
     pub const fn new(
         unstable_paths: &'static [&'static str],
         stable_paths: &'static [(MatrixVersion, &'static str)],
         deprecated: Option<MatrixVersion>,
         removed: Option<MatrixVersion>,
     ) -> Self {
+        match Self::try_new(unstable_paths, stable_paths, deprecated, removed) {
+            Ok(history) => history,
+            Err(error) => panic!("{}", error.as_str()),
+        }
+    }
+
+    /// Constructs an instance of [`VersionHistory`], returning an error if it does not pass
+    /// invariants.
+    ///
+    /// This checks the same invariants as [`VersionHistory::new()`], it is meant to be used for
+    /// endpoints that are defined at runtime.
+    pub const fn try_new(
+        unstable_paths: &'static [&'static str],
+        stable_paths: &'static [(MatrixVersion, &'static str)],
+        deprecated: Option<MatrixVersion>,
+        removed: Option<MatrixVersion>,
+    ) -> Result<Self, VersionHistoryError> {
         use konst::{iter, slice, string};
 
-        const fn check_path_is_valid(path: &'static str) {
+        const fn check_path_is_valid(path: &'static str) -> Result<(), VersionHistoryError> {
             iter::for_each!(path_b in slice::iter(path.as_bytes()) => {
                 match *path_b {
                     0x21..=0x7E => {},
-                    _ => panic!("path contains invalid (non-ascii or whitespace) characters")
+                    _ => return Err(VersionHistoryError::InvalidPath),
                 }
             });
+
+            Ok(())
         }
 
-        const fn check_path_args_equal(first: &'static str, second: &'static str) {
+        const fn check_path_args_equal(
+            first: &'static str,
+            second: &'static str,
+        ) -> Result<(), VersionHistoryError> {
             let mut second_iter = string::split(second, "/").next();
 
             iter::for_each!(first_s in string::split(first, "/") => {
@@ -221,10 +416,10 @@ impl VersionHistory {
 
                     if let Some(second_next_arg) = second_next_arg {
                         if !string::eq_str(second_next_arg, first_arg) {
-                            panic!("Path Arguments do not match");
+                            return Err(VersionHistoryError::PathArgumentsMismatch);
                         }
                     } else {
-                        panic!("Amount of Path Arguments do not match");
+                        return Err(VersionHistoryError::PathArgumentsCountMismatch);
                     }
                 }
             });
@@ -232,10 +427,23 @@ impl VersionHistory {
             // If second iterator still has some values, empty first.
             while let Some((second_s, second_n_iter)) = second_iter {
                 if string::starts_with(second_s, ":") {
-                    panic!("Amount of Path Arguments do not match");
+                    return Err(VersionHistoryError::PathArgumentsCountMismatch);
                 }
                 second_iter = second_n_iter.next();
             }
+
+            Ok(())
+        }
+
+        const fn check_path(
+            ref_path: &'static str,
+            path: &'static str,
+        ) -> Result<(), VersionHistoryError> {
+            if let Err(error) = check_path_is_valid(path) {
+                return Err(error);
+            }
+
+            check_path_args_equal(ref_path, path)
         }
 
         // The path we're going to use to compare all other paths with
@@ -244,19 +452,21 @@ impl VersionHistory {
         } else if let Some((_, s)) = stable_paths.first() {
             s
         } else {
-            panic!("No paths supplied")
+            return Err(VersionHistoryError::NoPaths);
         };
 
         iter::for_each!(unstable_path in slice::iter(unstable_paths) => {
-            check_path_is_valid(unstable_path);
-            check_path_args_equal(ref_path, unstable_path);
+            if let Err(error) = check_path(ref_path, unstable_path) {
+                return Err(error);
+            }
         });
 
         let mut prev_seen_version: Option<MatrixVersion> = None;
 
         iter::for_each!(stable_path in slice::iter(stable_paths) => {
-            check_path_is_valid(stable_path.1);
-            check_path_args_equal(ref_path, stable_path.1);
+            if let Err(error) = check_path(ref_path, stable_path.1) {
+                return Err(error);
+            }
 
             let current_version = stable_path.0;
 
@@ -265,10 +475,10 @@ impl VersionHistory {
 
                 if cmp_result.is_eq() {
                     // Found a duplicate, current == previous
-                    panic!("Duplicate matrix version in stable_paths")
+                    return Err(VersionHistoryError::DuplicateStableVersion);
                 } else if cmp_result.is_lt() {
                     // Found an older version, current < previous
-                    panic!("No ascending order in stable_paths")
+                    return Err(VersionHistoryError::UnorderedStableVersions);
                 }
             }
 
@@ -282,13 +492,13 @@ impl VersionHistory {
                     // prev_seen_version == deprecated, except for 1.0.
                     // It is possible that an endpoint was both made stable and deprecated in the
                     // legacy versions.
-                    panic!("deprecated version is equal to latest stable path version")
+                    return Err(VersionHistoryError::DeprecatedEqualToStable);
                 } else if ord_result.is_gt() {
                     // prev_seen_version > deprecated
-                    panic!("deprecated version is older than latest stable path version")
+                    return Err(VersionHistoryError::DeprecatedOlderThanStable);
                 }
             } else {
-                panic!("Defined deprecated version while no stable path exists")
+                return Err(VersionHistoryError::DeprecatedWithoutStable);
             }
         }
 
@@ -297,17 +507,17 @@ impl VersionHistory {
                 let ord_result = deprecated.const_ord(&removed);
                 if ord_result.is_eq() {
                     // deprecated == removed
-                    panic!("removed version is equal to deprecated version")
+                    return Err(VersionHistoryError::RemovedEqualToDeprecated);
                 } else if ord_result.is_gt() {
                     // deprecated > removed
-                    panic!("removed version is older than deprecated version")
+                    return Err(VersionHistoryError::RemovedOlderThanDeprecated);
                 }
             } else {
-                panic!("Defined removed version while no deprecated version exists")
+                return Err(VersionHistoryError::RemovedWithoutDeprecated);
             }
         }
 
-        VersionHistory { unstable_paths, stable_paths, deprecated, removed }
+        Ok(VersionHistory { unstable_paths, stable_paths, deprecated, removed })
     }
 
     // This function helps picks the right path (or an error) from a set of Matrix versions.
@@ -347,7 +557,11 @@ impl VersionHistory {
                     .stable_endpoint_for(versions)
                     .expect("VersioningDecision::Stable implies that a stable path exists"))
             }
-            VersioningDecision::Unstable => self.unstable().ok_or(IntoHttpError::NoUnstablePath),
+            VersioningDecision::Unstable => self.unstable().ok_or(if versions.is_empty() {
+                IntoHttpError::NoSupportedVersion
+            } else {
+                IntoHttpError::NoUnstablePath
+            }),
         }
     }
 
@@ -393,6 +607,19 @@ impl VersionHistory {
         self.stable_paths.first().map(|(v, _)| *v)
     }
 
+    /// Whether this endpoint has at least one stable path.
+    pub fn is_stable(&self) -> bool {
+        !self.stable_paths.is_empty()
+    }
+
+    /// Whether this endpoint only has unstable paths.
+    ///
+    /// This is the case for endpoints that were not added to a released version of the Matrix
+    /// specification yet.
+    pub fn unstable_only(&self) -> bool {
+        !self.is_stable() && !self.unstable_paths.is_empty()
+    }
+
     /// Returns the Matrix version that deprecated this endpoint, if any.
     pub fn deprecated_in(&self) -> Option<MatrixVersion> {
         self.deprecated
@@ -418,6 +645,16 @@ impl VersionHistory {
         self.unstable_paths.iter().copied()
     }
 
+    /// The number of unstable path variants.
+    pub const fn unstable_path_count(&self) -> usize {
+        self.unstable_paths.len()
+    }
+
+    /// The number of stable path variants.
+    pub const fn stable_path_count(&self) -> usize {
+        self.stable_paths.len()
+    }
+
     /// Returns all stable path variants in canon form, with corresponding Matrix version.
     pub fn stable_paths(&self) -> impl Iterator<Item = (MatrixVersion, &'static str)> {
         self.stable_paths.iter().map(|(version, data)| (*version, *data))
@@ -445,6 +682,36 @@ impl VersionHistory {
 
         None
     }
+
+    /// The oldest version between `lower` and `upper` (inclusive) in which this endpoint has a
+    /// stable path.
+    ///
+    /// This is `lower` if the endpoint was already stable in that version, otherwise it is the
+    /// version in which the endpoint got its first stable path, if it is not after `upper`. This
+    /// can be used to find the oldest version in a range in which the endpoint is available.
+    ///
+    /// Returns `None` if the endpoint was removed in or before that version.
+    pub fn stable_in_range(
+        &self,
+        lower: MatrixVersion,
+        upper: MatrixVersion,
+    ) -> Option<MatrixVersion> {
+        if lower.const_ord(&upper).is_gt() {
+            return None;
+        }
+
+        let first_stable = self.added_in()?;
+        if first_stable.const_ord(&upper).is_gt() {
+            return None;
+        }
+
+        let oldest = if first_stable.const_ord(&lower).is_gt() { first_stable } else { lower };
+        if self.removed.is_some_and(|removed| removed.const_ord(&oldest).is_le()) {
+            return None;
+        }
+
+        Some(oldest)
+    }
 }
 
 /// A versioning "decision" derived from a set of Matrix versions.
@@ -541,6 +808,11 @@ pub enum MatrixVersion {
     ///
     /// See <https://spec.matrix.org/v1.10/>.
     V1_10,
+
+    /// Version 1.11 of the Matrix specification, released in Q2 2024.
+    ///
+    /// See <https://spec.matrix.org/v1.11/>.
+    V1_11,
 }
 
 impl TryFrom<&str> for MatrixVersion {
@@ -549,21 +821,27 @@ impl TryFrom<&str> for MatrixVersion {
     fn try_from(value: &str) -> Result<MatrixVersion, Self::Error> {
         use MatrixVersion::*;
 
-        Ok(match value {
-            // FIXME: these are likely not entirely correct; https://github.com/ruma/ruma/issues/852
-            "v1.0" |
-            // Additional definitions according to https://spec.matrix.org/latest/#legacy-versioning
-            "r0.5.0" | "r0.6.0" | "r0.6.1" => V1_0,
-            "v1.1" => V1_1,
-            "v1.2" => V1_2,
-            "v1.3" => V1_3,
-            "v1.4" => V1_4,
-            "v1.5" => V1_5,
-            "v1.6" => V1_6,
-            "v1.7" => V1_7,
-            "v1.8" => V1_8,
-            "v1.9" => V1_9,
-            "v1.10" => V1_10,
+        // FIXME: these are likely not entirely correct; https://github.com/ruma/ruma/issues/852
+        // Additional definitions according to https://spec.matrix.org/latest/#legacy-versioning
+        if matches!(value, "r0.5.0" | "r0.6.0" | "r0.6.1") {
+            return Ok(V1_0);
+        }
+
+        // Be lenient with the prefix, some servers and configuration files omit it or use an
+        // uppercase one.
+        Ok(match value.strip_prefix(['v', 'V']).unwrap_or(value) {
+            "1.0" => V1_0,
+            "1.1" => V1_1,
+            "1.2" => V1_2,
+            "1.3" => V1_3,
+            "1.4" => V1_4,
+            "1.5" => V1_5,
+            "1.6" => V1_6,
+            "1.7" => V1_7,
+            "1.8" => V1_8,
+            "1.9" => V1_9,
+            "1.10" => V1_10,
+            "1.11" => V1_11,
             _ => return Err(UnknownVersionError),
         })
     }
@@ -592,11 +870,25 @@ impl MatrixVersion {
     /// to prune major version differences, and versions too new for `self`.
     ///
     /// This (considering if major versions are the same) is equivalent to a `self >= other`
-    /// check.
+    /// check. Versions with different major versions are never compatible, see
+    /// [`MatrixVersion::same_major()`].
     pub fn is_superset_of(self, other: Self) -> bool {
-        let (major_l, minor_l) = self.into_parts();
-        let (major_r, minor_r) = other.into_parts();
-        major_l == major_r && minor_l >= minor_r
+        self.same_major(other) && self.minor() >= other.minor()
+    }
+
+    /// Whether this version has the same major version as the other.
+    pub const fn same_major(self, other: Self) -> bool {
+        self.major() == other.major()
+    }
+
+    /// The major number of this version.
+    pub const fn major(self) -> u8 {
+        self.into_parts().0
+    }
+
+    /// The minor number of this version.
+    pub const fn minor(self) -> u8 {
+        self.into_parts().1
     }
 
     /// Decompose the Matrix version into its major and minor number.
@@ -613,6 +905,7 @@ impl MatrixVersion {
             MatrixVersion::V1_8 => (1, 8),
             MatrixVersion::V1_9 => (1, 9),
             MatrixVersion::V1_10 => (1, 10),
+            MatrixVersion::V1_11 => (1, 11),
         }
     }
 
@@ -630,10 +923,76 @@ impl MatrixVersion {
             (1, 8) => Ok(MatrixVersion::V1_8),
             (1, 9) => Ok(MatrixVersion::V1_9),
             (1, 10) => Ok(MatrixVersion::V1_10),
+            (1, 11) => Ok(MatrixVersion::V1_11),
             _ => Err(UnknownVersionError),
         }
     }
 
+    /// All known versions, from oldest to newest.
+    const ALL: &'static [Self] = &[
+        MatrixVersion::V1_0,
+        MatrixVersion::V1_1,
+        MatrixVersion::V1_2,
+        MatrixVersion::V1_3,
+        MatrixVersion::V1_4,
+        MatrixVersion::V1_5,
+        MatrixVersion::V1_6,
+        MatrixVersion::V1_7,
+        MatrixVersion::V1_8,
+        MatrixVersion::V1_9,
+        MatrixVersion::V1_10,
+        MatrixVersion::V1_11,
+    ];
+
+    /// Iterate over all known versions, from oldest to newest.
+    pub fn all() -> impl DoubleEndedIterator<Item = MatrixVersion> {
+        Self::ALL.iter().copied()
+    }
+
+    /// The version following this one, if it is known.
+    pub fn next(self) -> Option<Self> {
+        let idx = Self::ALL.iter().position(|v| *v == self)?;
+        Self::ALL.get(idx + 1).copied()
+    }
+
+    /// The version preceding this one, if any.
+    pub fn previous(self) -> Option<Self> {
+        let idx = Self::ALL.iter().position(|v| *v == self)?;
+        idx.checked_sub(1).map(|idx| Self::ALL[idx])
+    }
+
+    /// The string used to advertise this version, as returned by `GET /_matrix/client/versions`.
+    ///
+    /// This is the same as the `Display` representation, e.g. `v1.11`.
+    pub const fn canonical_str(self) -> &'static str {
+        match self {
+            MatrixVersion::V1_0 => "v1.0",
+            MatrixVersion::V1_1 => "v1.1",
+            MatrixVersion::V1_2 => "v1.2",
+            MatrixVersion::V1_3 => "v1.3",
+            MatrixVersion::V1_4 => "v1.4",
+            MatrixVersion::V1_5 => "v1.5",
+            MatrixVersion::V1_6 => "v1.6",
+            MatrixVersion::V1_7 => "v1.7",
+            MatrixVersion::V1_8 => "v1.8",
+            MatrixVersion::V1_9 => "v1.9",
+            MatrixVersion::V1_10 => "v1.10",
+            MatrixVersion::V1_11 => "v1.11",
+        }
+    }
+
+    /// The [legacy version strings] that are accepted as aliases of this version.
+    ///
+    /// Only [`MatrixVersion::V1_0`] has legacy aliases.
+    ///
+    /// [legacy version strings]: https://spec.matrix.org/latest/#legacy-versioning
+    pub const fn legacy_versions(self) -> &'static [&'static str] {
+        match self {
+            MatrixVersion::V1_0 => &["r0.5.0", "r0.6.0", "r0.6.1"],
+            _ => &[],
+        }
+    }
+
     /// Constructor for use by the `metadata!` macro.
     ///
     /// Accepts string literals and parses them.
@@ -724,15 +1083,74 @@ impl MatrixVersion {
             // <https://spec.matrix.org/v1.9/rooms/#complete-list-of-room-versions>
             | MatrixVersion::V1_9
             // <https://spec.matrix.org/v1.10/rooms/#complete-list-of-room-versions>
-            | MatrixVersion::V1_10 => RoomVersionId::V10,
+            | MatrixVersion::V1_10
+            // <https://spec.matrix.org/v1.11/rooms/#complete-list-of-room-versions>
+            | MatrixVersion::V1_11 => RoomVersionId::V10,
+        }
+    }
+
+    /// Get the earliest `MatrixVersion` that uses the given [`RoomVersionId`] as its default room
+    /// version.
+    ///
+    /// Returns `None` if the room version was never the default room version.
+    pub fn first_version_with_default_room_version(
+        room_version: &RoomVersionId,
+    ) -> Option<MatrixVersion> {
+        Self::all().find(|version| version.default_room_version() == *room_version)
+    }
+
+    /// Get the minimum `MatrixVersion` that is required for the given feature.
+    pub fn required_for(feature: KnownFeature) -> MatrixVersion {
+        match feature {
+            KnownFeature::SpaceHierarchy => MatrixVersion::V1_2,
+            KnownFeature::Threads => MatrixVersion::V1_4,
+            KnownFeature::AsyncUploads => MatrixVersion::V1_7,
+            KnownFeature::AuthenticatedMedia => MatrixVersion::V1_11,
         }
     }
 }
 
+impl Serialize for MatrixVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MatrixVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = crate::serde::deserialize_cow_str(deserializer)?;
+        Self::try_from(s.as_ref()).map_err(de::Error::custom)
+    }
+}
+
+/// Behaviors of the Matrix specification that are only available from a given [`MatrixVersion`].
+///
+/// Use [`MatrixVersion::required_for()`] to get the minimum version supporting a feature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum KnownFeature {
+    /// The `/hierarchy` endpoint to browse spaces.
+    SpaceHierarchy,
+
+    /// Threads, with the `m.thread` relation type.
+    Threads,
+
+    /// Asynchronous media uploads, with the `/create` endpoint.
+    AsyncUploads,
+
+    /// Authenticated media, with the `/_matrix/client/v1/media` endpoints.
+    AuthenticatedMedia,
+}
+
 impl Display for MatrixVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let (major, minor) = self.into_parts();
-        f.write_str(&format!("v{major}.{minor}"))
+        f.write_str(self.canonical_str())
     }
 }
 
@@ -740,13 +1158,17 @@ impl Display for MatrixVersion {
 mod tests {
     use assert_matches2::assert_matches;
     use http::Method;
+    use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
     use super::{
-        AuthScheme,
-        MatrixVersion::{self, V1_0, V1_1, V1_2, V1_3},
-        Metadata, VersionHistory,
+        AuthScheme, BodyFormat, KnownFeature,
+        MatrixVersion::{self, V1_0, V1_1, V1_10, V1_11, V1_2, V1_3, V1_4, V1_5, V1_8},
+        Metadata, PreEncoded, SendAccessToken, TokenKind, VersionHistory,
+    };
+    use crate::{
+        api::error::{IntoHttpError, VersionHistoryError},
+        RoomVersionId,
     };
-    use crate::api::error::IntoHttpError;
 
     fn stable_only_metadata(stable_paths: &'static [(MatrixVersion, &'static str)]) -> Metadata {
         Metadata {
@@ -759,6 +1181,7 @@ mod tests {
                 deprecated: None,
                 removed: None,
             },
+            body_format: BodyFormat::Json,
         }
     }
 
@@ -793,6 +1216,70 @@ mod tests {
         assert_eq!(url, "https://example.org/s/%23path");
     }
 
+    #[test]
+    fn make_endpoint_url_raw_with_pre_encoded_args() {
+        let meta = stable_only_metadata(&[(V1_0, "/s/:x/:y")]);
+        let url = meta
+            .make_endpoint_url_raw(
+                &[V1_0],
+                "https://example.org",
+                &[&PreEncoded("%23path"), &"#path"],
+                "",
+            )
+            .unwrap();
+        assert_eq!(url, "https://example.org/s/%23path/%23path");
+    }
+
+    #[test]
+    fn authorization_query_param() {
+        let mut meta = stable_only_metadata(&[(V1_0, "/s")]);
+        meta.authentication = AuthScheme::AccessToken;
+
+        assert_eq!(
+            meta.authorization_query_param(SendAccessToken::IfRequired("secret")).unwrap(),
+            Some(("access_token".to_owned(), "secret".to_owned()))
+        );
+        assert_matches!(
+            meta.authorization_query_param(SendAccessToken::None),
+            Err(IntoHttpError::NeedsAuthentication)
+        );
+
+        meta.authentication = AuthScheme::None;
+        assert_eq!(
+            meta.authorization_query_param(SendAccessToken::IfRequired("secret")).unwrap(),
+            None
+        );
+        assert_eq!(
+            meta.authorization_query_param(SendAccessToken::Always("secret")).unwrap(),
+            Some(("access_token".to_owned(), "secret".to_owned()))
+        );
+    }
+
+    #[test]
+    fn accepted_tokens() {
+        let mut meta = stable_only_metadata(&[(V1_0, "/s")]);
+        assert_eq!(meta.accepted_tokens(), &[]);
+
+        meta.authentication = AuthScheme::AppserviceToken;
+        assert_eq!(meta.accepted_tokens(), &[TokenKind::AppserviceToken]);
+
+        meta.authentication = AuthScheme::AccessToken;
+        assert_eq!(meta.accepted_tokens(), &[TokenKind::AccessToken, TokenKind::AppserviceToken]);
+
+        meta.authentication = AuthScheme::ServerSignatures;
+        assert_eq!(meta.accepted_tokens(), &[]);
+    }
+
+    #[test]
+    fn canonical_path() {
+        let meta = stable_only_metadata(&[(V1_0, "/s/v1.0/:x"), (V1_1, "/s/v1.1/:x")]);
+        assert_eq!(meta.canonical_path(), "/s/v1.1/:x");
+
+        let mut meta = stable_only_metadata(&[]);
+        meta.history.unstable_paths = &["/u/1/:x", "/u/2/:x"];
+        assert_eq!(meta.canonical_path(), "/u/2/:x");
+    }
+
     #[test]
     fn make_endpoint_url_with_query() {
         let meta = stable_only_metadata(&[(V1_0, "/s/")]);
@@ -800,6 +1287,30 @@ mod tests {
         assert_eq!(url, "https://example.org/s/?foo=bar");
     }
 
+    #[test]
+    fn make_endpoint_url_with_trailing_slash_base() {
+        let meta = stable_only_metadata(&[(V1_0, "/s")]);
+        let url = meta.make_endpoint_url(&[V1_0], "https://example.org/", &[], "").unwrap();
+        assert_eq!(url, "https://example.org/s");
+    }
+
+    #[test]
+    fn make_endpoint_url_with_invalid_base() {
+        let meta = stable_only_metadata(&[(V1_0, "/s")]);
+        assert_matches!(
+            meta.make_endpoint_url(&[V1_0], "example.org", &[], ""),
+            Err(IntoHttpError::InvalidBaseUrl)
+        );
+        assert_matches!(
+            meta.make_endpoint_url(&[V1_0], "ftp://example.org", &[], ""),
+            Err(IntoHttpError::InvalidBaseUrl)
+        );
+        assert_matches!(
+            meta.make_endpoint_url(&[V1_0], "https://exa mple.org", &[], ""),
+            Err(IntoHttpError::InvalidBaseUrl)
+        );
+    }
+
     #[test]
     #[should_panic]
     fn make_endpoint_url_wrong_num_path_args() {
@@ -810,6 +1321,29 @@ mod tests {
     const EMPTY: VersionHistory =
         VersionHistory { unstable_paths: &[], stable_paths: &[], deprecated: None, removed: None };
 
+    #[test]
+    fn version_history_try_new() {
+        let hist = VersionHistory::try_new(&["/u/:x"], &[(V1_0, "/s/:x")], Some(V1_1), None);
+        assert_matches!(hist, Ok(_));
+
+        assert_eq!(
+            VersionHistory::try_new(&[], &[], None, None).unwrap_err(),
+            VersionHistoryError::NoPaths
+        );
+        assert_eq!(
+            VersionHistory::try_new(&["/u/:x"], &[(V1_0, "/s/:y")], None, None).unwrap_err(),
+            VersionHistoryError::PathArgumentsMismatch
+        );
+        assert_eq!(
+            VersionHistory::try_new(&[], &[(V1_1, "/s"), (V1_0, "/s")], None, None).unwrap_err(),
+            VersionHistoryError::UnorderedStableVersions
+        );
+        assert_eq!(
+            VersionHistory::try_new(&["/u"], &[], None, Some(V1_1)).unwrap_err(),
+            VersionHistoryError::RemovedWithoutDeprecated
+        );
+    }
+
     #[test]
     fn select_latest_stable() {
         let hist = VersionHistory { stable_paths: &[(V1_1, "/s")], ..EMPTY };
@@ -856,10 +1390,184 @@ mod tests {
         assert_matches!(hist.select_path(&[V1_0]), Err(IntoHttpError::NoUnstablePath));
     }
 
+    #[test]
+    fn no_versions() {
+        let hist = VersionHistory { stable_paths: &[(V1_1, "/s")], ..EMPTY };
+        assert_matches!(hist.select_path(&[]), Err(IntoHttpError::NoSupportedVersion));
+
+        let hist =
+            VersionHistory { unstable_paths: &["/u"], stable_paths: &[(V1_1, "/s")], ..EMPTY };
+        assert_matches!(hist.select_path(&[]), Ok("/u"));
+    }
+
+    #[test]
+    fn stable_only_history() {
+        let hist = VersionHistory { stable_paths: &[(V1_1, "/s")], ..EMPTY };
+        assert!(hist.is_stable());
+        assert!(!hist.unstable_only());
+    }
+
+    #[test]
+    fn unstable_only_history() {
+        let hist = VersionHistory { unstable_paths: &["/u"], ..EMPTY };
+        assert!(!hist.is_stable());
+        assert!(hist.unstable_only());
+
+        let hist =
+            VersionHistory { unstable_paths: &["/u"], stable_paths: &[(V1_1, "/s")], ..EMPTY };
+        assert!(hist.is_stable());
+        assert!(!hist.unstable_only());
+    }
+
+    #[test]
+    fn first_version_with_default_room_version() {
+        assert_eq!(
+            MatrixVersion::first_version_with_default_room_version(&RoomVersionId::V6),
+            Some(V1_0)
+        );
+        assert_eq!(
+            MatrixVersion::first_version_with_default_room_version(&RoomVersionId::V9),
+            Some(V1_3)
+        );
+        assert_eq!(
+            MatrixVersion::first_version_with_default_room_version(&RoomVersionId::V10),
+            Some(MatrixVersion::V1_6)
+        );
+        assert_eq!(
+            MatrixVersion::first_version_with_default_room_version(&RoomVersionId::V1),
+            None
+        );
+    }
+
+    #[test]
+    fn iterate_versions() {
+        assert_eq!(MatrixVersion::all().next(), Some(V1_0));
+        assert_eq!(MatrixVersion::all().next_back(), Some(V1_11));
+
+        assert_eq!(V1_0.previous(), None);
+        assert_eq!(V1_0.next(), Some(V1_1));
+        assert_eq!(V1_11.previous(), Some(V1_10));
+        assert_eq!(V1_11.next(), None);
+
+        let mut version = V1_0;
+        for expected in MatrixVersion::all().skip(1) {
+            let next = version.next().unwrap();
+            assert_eq!(next, expected);
+            assert_eq!(next.previous(), Some(version));
+            version = next;
+        }
+    }
+
+    #[test]
+    fn stable_in_range() {
+        let hist = VersionHistory { stable_paths: &[(V1_1, "/s1"), (V1_5, "/s5")], ..EMPTY };
+        assert_eq!(hist.stable_in_range(V1_0, V1_11), Some(V1_1));
+        assert_eq!(hist.stable_in_range(V1_3, V1_8), Some(V1_3));
+        assert_eq!(hist.stable_in_range(V1_5, V1_5), Some(V1_5));
+        assert_eq!(hist.stable_in_range(V1_2, V1_4), Some(V1_2));
+        assert_eq!(hist.stable_in_range(V1_8, V1_11), Some(V1_8));
+        assert_eq!(hist.stable_in_range(V1_4, V1_2), None);
+        assert_eq!(EMPTY.stable_in_range(V1_0, V1_11), None);
+
+        let hist = VersionHistory { stable_paths: &[(V1_5, "/s5")], ..EMPTY };
+        assert_eq!(hist.stable_in_range(V1_1, V1_4), None);
+
+        let hist = VersionHistory {
+            stable_paths: &[(V1_1, "/s1")],
+            deprecated: Some(V1_2),
+            removed: Some(V1_3),
+            ..EMPTY
+        };
+        assert_eq!(hist.stable_in_range(V1_0, V1_8), Some(V1_1));
+        assert_eq!(hist.stable_in_range(V1_2, V1_8), Some(V1_2));
+        assert_eq!(hist.stable_in_range(V1_3, V1_8), None);
+        assert_eq!(hist.stable_in_range(V1_4, V1_8), None);
+    }
+
+    #[test]
+    fn path_counts() {
+        const HIST: VersionHistory = VersionHistory {
+            unstable_paths: &["/u1", "/u2"],
+            stable_paths: &[(V1_1, "/s")],
+            ..EMPTY
+        };
+        const PATHS: [&str; HIST.unstable_path_count() + HIST.stable_path_count()] =
+            ["/u1", "/u2", "/s"];
+
+        assert_eq!(HIST.unstable_path_count(), 2);
+        assert_eq!(HIST.stable_path_count(), 1);
+        assert!(HIST.all_paths().eq(PATHS));
+    }
+
     #[test]
     fn version_literal() {
         const LIT: MatrixVersion = MatrixVersion::from_lit("1.0");
 
         assert_eq!(LIT, V1_0);
     }
+
+    #[test]
+    fn major_minor() {
+        assert_eq!(V1_0.major(), 1);
+        assert_eq!(V1_0.minor(), 0);
+        assert_eq!(V1_11.major(), 1);
+        assert_eq!(V1_11.minor(), 11);
+    }
+
+    #[test]
+    fn same_major() {
+        assert!(V1_0.same_major(V1_11));
+        assert!(V1_3.same_major(V1_2));
+
+        assert!(V1_3.is_superset_of(V1_2));
+        assert!(V1_3.is_superset_of(V1_3));
+        assert!(!V1_2.is_superset_of(V1_3));
+    }
+
+    #[test]
+    fn serialize_matrix_version() {
+        assert_eq!(to_json_value(V1_0).unwrap(), json!("v1.0"));
+        assert_eq!(to_json_value(V1_11).unwrap(), json!("v1.11"));
+    }
+
+    #[test]
+    fn deserialize_matrix_version() {
+        assert_eq!(from_json_value::<MatrixVersion>(json!("r0.6.1")).unwrap(), V1_0);
+        assert_eq!(from_json_value::<MatrixVersion>(json!("v1.2")).unwrap(), V1_2);
+        from_json_value::<MatrixVersion>(json!("v0.9")).unwrap_err();
+    }
+
+    #[test]
+    fn parse_version_without_canonical_prefix() {
+        assert_eq!(MatrixVersion::try_from("v1.5").unwrap(), V1_5);
+        assert_eq!(MatrixVersion::try_from("V1.5").unwrap(), V1_5);
+        assert_eq!(MatrixVersion::try_from("1.5").unwrap(), V1_5);
+        assert_eq!(V1_5.to_string(), "v1.5");
+
+        MatrixVersion::try_from("vv1.5").unwrap_err();
+        MatrixVersion::try_from("v1.5.0").unwrap_err();
+        MatrixVersion::try_from("vr0.6.1").unwrap_err();
+        MatrixVersion::try_from("R0.6.1").unwrap_err();
+    }
+
+    #[test]
+    fn version_strings_round_trip() {
+        for version in [V1_0, V1_2, V1_11] {
+            assert_eq!(version.to_string(), version.canonical_str());
+            assert_eq!(MatrixVersion::try_from(version.canonical_str()).unwrap(), version);
+
+            for legacy in version.legacy_versions() {
+                assert_eq!(MatrixVersion::try_from(*legacy).unwrap(), version);
+            }
+        }
+
+        assert_eq!(V1_0.legacy_versions(), ["r0.5.0", "r0.6.0", "r0.6.1"]);
+        assert!(V1_11.legacy_versions().is_empty());
+    }
+
+    #[test]
+    fn required_for_feature() {
+        assert_eq!(MatrixVersion::required_for(KnownFeature::AuthenticatedMedia), V1_11);
+        assert_eq!(MatrixVersion::required_for(KnownFeature::SpaceHierarchy), V1_2);
+    }
 }
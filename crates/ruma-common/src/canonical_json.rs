@@ -3,7 +3,7 @@
 use std::{fmt, mem};
 
 use serde::Serialize;
-use serde_json::Value as JsonValue;
+use serde_json::{value::RawValue as RawJsonValue, Value as JsonValue};
 
 mod value;
 
@@ -115,6 +115,26 @@ pub fn to_canonical_value<T: Serialize>(
     serde_json::to_value(value).map_err(CanonicalJsonError::SerDe)?.try_into()
 }
 
+/// Whether sending a state event with the given content would not change the current state.
+///
+/// The contents are compared as canonical JSON, so the order of the keys and the whitespace are
+/// ignored. Returns `false` if there is no current state, or if one of the contents is not valid
+/// canonical JSON.
+pub fn is_redundant_state(
+    new_content: &RawJsonValue,
+    current_content: Option<&RawJsonValue>,
+) -> bool {
+    let Some(current_content) = current_content else {
+        return false;
+    };
+
+    let parse = |json: &RawJsonValue| serde_json::from_str::<CanonicalJsonValue>(json.get());
+    match (parse(new_content), parse(current_content)) {
+        (Ok(new_content), Ok(current_content)) => new_content == current_content,
+        _ => false,
+    }
+}
+
 /// The value to put in `unsigned.redacted_because`.
 #[derive(Clone, Debug)]
 pub struct RedactedBecause(CanonicalJsonObject);
@@ -505,13 +525,30 @@ mod tests {
     use js_int::int;
     use serde_json::{
         from_str as from_json_str, json, to_string as to_json_string, to_value as to_json_value,
+        value::RawValue as RawJsonValue,
     };
 
     use super::{
-        redact_in_place, to_canonical_value, try_from_json_map, value::CanonicalJsonValue,
+        is_redundant_state, redact_in_place, to_canonical_value, try_from_json_map,
+        value::CanonicalJsonValue,
     };
     use crate::RoomVersionId;
 
+    #[test]
+    fn redundant_state() {
+        let raw = |json: &str| RawJsonValue::from_string(json.to_owned()).unwrap();
+        let current = raw(r#"{ "name": "Room", "extra": { "a": 1, "b": [true] } }"#);
+
+        let identical = raw(r#"{"extra":{"b":[true],"a":1},"name":"Room"}"#);
+        assert!(is_redundant_state(&identical, Some(&current)));
+
+        let changed = raw(r#"{ "name": "New room", "extra": { "a": 1, "b": [true] } }"#);
+        assert!(!is_redundant_state(&changed, Some(&current)));
+
+        assert!(!is_redundant_state(&identical, None));
+        assert!(!is_redundant_state(&raw(r#"{ "a": 1.5 }"#), Some(&raw(r#"{ "a": 1.5 }"#))));
+    }
+
     #[test]
     fn serialize_canon() {
         let json: CanonicalJsonValue = json!({
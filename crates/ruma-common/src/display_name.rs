@@ -0,0 +1,71 @@
+//! Helpers for rendering the display names of users.
+
+use std::collections::BTreeMap;
+
+use crate::{OwnedUserId, UserId};
+
+/// Computes the name to show for a user, according to the [display name calculation] of the spec.
+///
+/// `all_names` should contain the display names of all the members of the room, including
+/// `user_id`.
+///
+/// Returns the display name as-is if it is unique. If another user in `all_names` has the same
+/// display name, or if the display name looks like a user ID (i.e. it starts with `@`), the user
+/// ID is appended to disambiguate it, like `name (@user:example.org)`. If the display name is
+/// empty, the user ID is returned instead.
+///
+/// [display name calculation]: https://spec.matrix.org/latest/client-server-api/#calculating-the-display-name-for-a-user
+pub fn disambiguate(
+    display_name: &str,
+    user_id: &UserId,
+    all_names: &BTreeMap<OwnedUserId, String>,
+) -> String {
+    if display_name.is_empty() {
+        return user_id.to_string();
+    }
+
+    let is_ambiguous = display_name.starts_with('@')
+        || all_names.iter().any(|(other_id, name)| other_id != user_id && name == display_name);
+
+    if is_ambiguous {
+        format!("{display_name} ({user_id})")
+    } else {
+        display_name.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::disambiguate;
+    use crate::{owned_user_id, user_id};
+
+    #[test]
+    fn disambiguate_display_names() {
+        let alice = user_id!("@alice:example.org");
+        let other_alice = user_id!("@alice:example.com");
+        let mut all_names = BTreeMap::from([
+            (alice.to_owned(), "Alice".to_owned()),
+            (owned_user_id!("@bob:example.org"), "Bob".to_owned()),
+        ]);
+
+        // Unique name.
+        assert_eq!(disambiguate("Alice", alice, &all_names), "Alice");
+
+        // Duplicate name.
+        all_names.insert(other_alice.to_owned(), "Alice".to_owned());
+        assert_eq!(disambiguate("Alice", alice, &all_names), "Alice (@alice:example.org)");
+        assert_eq!(disambiguate("Alice", other_alice, &all_names), "Alice (@alice:example.com)");
+        assert_eq!(disambiguate("Bob", user_id!("@bob:example.org"), &all_names), "Bob");
+
+        // Name that looks like a user ID.
+        assert_eq!(
+            disambiguate("@bob:example.org", alice, &all_names),
+            "@bob:example.org (@alice:example.org)"
+        );
+
+        // Empty name.
+        assert_eq!(disambiguate("", alice, &all_names), "@alice:example.org");
+    }
+}
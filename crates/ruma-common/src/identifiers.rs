@@ -5,9 +5,12 @@
 #![allow(unused_qualifications)]
 
 #[doc(inline)]
-pub use ruma_identifiers_validation::error::{
-    Error as IdParseError, MatrixIdError, MatrixToError, MatrixUriError, MxcUriError,
-    VoipVersionIdError,
+pub use ruma_identifiers_validation::{
+    error::{
+        Error as IdParseError, MatrixIdError, MatrixToError, MatrixUriError, MxcUriError,
+        VoipVersionIdError,
+    },
+    ID_MAX_BYTES,
 };
 use serde::de::{self, Deserializer, Unexpected};
 
@@ -29,9 +32,9 @@ pub use self::{
     mxc_uri::{MxcUri, OwnedMxcUri},
     room_alias_id::{OwnedRoomAliasId, RoomAliasId},
     room_id::{OwnedRoomId, RoomId},
-    room_or_alias_id::{OwnedRoomOrAliasId, RoomOrAliasId},
+    room_or_alias_id::{OwnedRoomOrAliasId, RoomIdOrAliasId, RoomOrAliasId},
     room_version_id::RoomVersionId,
-    server_name::{OwnedServerName, ServerName},
+    server_name::{OwnedServerName, ResolvedServerName, ServerName},
     session_id::{OwnedSessionId, SessionId},
     signatures::{DeviceSignatures, EntitySignatures, ServerSignatures, Signatures},
     transaction_id::{OwnedTransactionId, TransactionId},
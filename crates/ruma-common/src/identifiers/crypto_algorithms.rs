@@ -1,5 +1,7 @@
 //! Key algorithms used in Matrix spec.
 
+use std::{convert::Infallible, str::FromStr};
+
 use ruma_macros::StringEnum;
 
 use crate::PrivOwnedStr;
@@ -36,6 +38,14 @@ pub enum SigningKeyAlgorithm {
     _Custom(PrivOwnedStr),
 }
 
+impl FromStr for SigningKeyAlgorithm {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
 /// An encryption algorithm to be used to encrypt messages sent to a room.
 #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/doc/string_enum.md"))]
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, StringEnum)]
@@ -1,7 +1,7 @@
 use ruma_macros::IdZst;
 
 #[cfg(feature = "rand")]
-use super::generate_localpart;
+use super::{generate_localpart, IdParseError};
 
 /// A Matrix key ID.
 ///
@@ -40,8 +40,50 @@ impl DeviceId {
     }
 }
 
+impl OwnedDeviceId {
+    /// Generates a random `OwnedDeviceId` of the given length, suitable for assignment to a new
+    /// device.
+    ///
+    /// Returns an error if `length` is `0`, since device IDs can't be empty, or if it exceeds the
+    /// maximum length of identifiers, [`ID_MAX_BYTES`](crate::ID_MAX_BYTES).
+    #[cfg(feature = "rand")]
+    pub fn with_length(length: usize) -> Result<Self, IdParseError> {
+        if length == 0 {
+            return Err(IdParseError::Empty);
+        }
+
+        #[cfg(not(feature = "compat-arbitrary-length-ids"))]
+        if length > crate::ID_MAX_BYTES {
+            return Err(IdParseError::MaximumLengthExceeded);
+        }
+
+        Ok(DeviceId::from_borrowed(&generate_localpart(length)).to_owned())
+    }
+
+    /// Generates a random `OwnedDeviceId` of the given length, suitable for assignment to a new
+    /// device.
+    ///
+    /// This is like [`with_length()`](Self::with_length), but panics instead of returning an
+    /// error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `length` is `0`, since device IDs can't be empty, or if it exceeds the maximum
+    /// length of identifiers, [`ID_MAX_BYTES`](crate::ID_MAX_BYTES).
+    #[cfg(feature = "rand")]
+    pub fn new_with_length(length: usize) -> Self {
+        assert!(length > 0, "device ID length must be greater than 0");
+        Self::with_length(length).expect("device ID length must not exceed ID_MAX_BYTES")
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "rand")]
+    use assert_matches2::assert_matches;
+
+    #[cfg(feature = "rand")]
+    use super::IdParseError;
     use super::{DeviceId, OwnedDeviceId};
 
     #[cfg(feature = "rand")]
@@ -50,6 +92,38 @@ mod tests {
         assert_eq!(DeviceId::new().as_str().len(), 8);
     }
 
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generate_device_id_with_length() {
+        assert_eq!(OwnedDeviceId::with_length(12).unwrap().as_str().len(), 12);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generate_device_id_new_with_length() {
+        assert_eq!(OwnedDeviceId::new_with_length(1).as_str().len(), 1);
+        assert_eq!(OwnedDeviceId::new_with_length(32).as_str().len(), 32);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    #[should_panic = "device ID length must be greater than 0"]
+    fn generate_device_id_new_with_length_zero() {
+        OwnedDeviceId::new_with_length(0);
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generate_device_id_with_length_zero() {
+        assert_matches!(OwnedDeviceId::with_length(0), Err(IdParseError::Empty));
+    }
+
+    #[cfg(all(feature = "rand", not(feature = "compat-arbitrary-length-ids")))]
+    #[test]
+    fn generate_device_id_with_length_too_long() {
+        assert_matches!(OwnedDeviceId::with_length(256), Err(IdParseError::MaximumLengthExceeded));
+    }
+
     #[test]
     fn create_device_id_from_str() {
         let ref_id: &DeviceId = "abcdefgh".into();
@@ -105,6 +105,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_id_parts() {
+        let event_id = <&EventId>::try_from("$39hvsi03hlne:example.com").unwrap();
+        assert_eq!(event_id.localpart(), "39hvsi03hlne");
+        assert_eq!(event_id.server_name().unwrap(), "example.com");
+
+        let event_id =
+            <&EventId>::try_from("$Rqnc-F-dvnEYJTyHq_iKxU2bZ1CI92-kuZq3a5lr5Zg").unwrap();
+        assert_eq!(event_id.localpart(), "Rqnc-F-dvnEYJTyHq_iKxU2bZ1CI92-kuZq3a5lr5Zg");
+        assert_eq!(event_id.server_name(), None);
+    }
+
     #[cfg(feature = "rand")]
     #[test]
     fn generate_random_valid_event_id() {
@@ -13,7 +13,10 @@ use super::{
     EventId, OwnedEventId, OwnedRoomAliasId, OwnedRoomId, OwnedRoomOrAliasId, OwnedServerName,
     OwnedUserId, RoomAliasId, RoomId, RoomOrAliasId, UserId,
 };
-use crate::{percent_encode::PATH_PERCENT_ENCODE_SET, PrivOwnedStr, ServerName};
+use crate::{
+    percent_encode::{encode_query_value, PATH_PERCENT_ENCODE_SET},
+    PrivOwnedStr, ServerName,
+};
 
 const MATRIX_TO_BASE_URL: &str = "https://matrix.to/#/";
 const MATRIX_SCHEME: &str = "matrix";
@@ -515,7 +518,7 @@ impl fmt::Display for MatrixUri {
 
         if let Some(action) = self.action() {
             f.write_str(if first { "?action=" } else { "&action=" })?;
-            f.write_str(action.as_str())?;
+            f.write_str(&encode_query_value(action.as_str()))?;
         }
 
         Ok(())
@@ -558,6 +561,15 @@ mod tests {
             user_id!("@jplatte:notareal.hs").matrix_to_uri().to_string(),
             "https://matrix.to/#/@jplatte:notareal.hs"
         );
+        assert_eq!(
+            user_id!("@jplatte:notareal.hs")
+                .matrix_to_uri_via(vec![
+                    server_name!("example.org"),
+                    server_name!("alt.example.org")
+                ])
+                .to_string(),
+            "https://matrix.to/#/@jplatte:notareal.hs?via=example.org&via=alt.example.org"
+        );
         assert_eq!(
             room_alias_id!("#ruma:notareal.hs").matrix_to_uri().to_string(),
             "https://matrix.to/#/%23ruma:notareal.hs"
@@ -793,6 +805,27 @@ mod tests {
         assert_eq!(matrix_to.via().len(), 0);
     }
 
+    #[test]
+    fn matrixtouri_event_permalink_round_trip() {
+        let via = [server_name!("notareal.hs"), server_name!("anotherunreal.hs")];
+        let matrix_to = room_id!("!ruma:notareal.hs")
+            .matrix_to_event_uri_via(event_id!("$event:notareal.hs"), via);
+
+        let uri = matrix_to.to_string();
+        assert_eq!(
+            uri,
+            "https://matrix.to/#/!ruma:notareal.hs/$event:notareal.hs?via=notareal.hs&via=anotherunreal.hs"
+        );
+
+        let parsed = MatrixToUri::parse(&uri).expect("Failed to create MatrixToUri.");
+        assert_eq!(
+            parsed.id(),
+            &(room_id!("!ruma:notareal.hs"), event_id!("$event:notareal.hs")).into()
+        );
+        assert_eq!(parsed.via(), via.map(ToOwned::to_owned));
+        assert_eq!(parsed, matrix_to);
+    }
+
     #[test]
     fn parse_matrixtouri_wrong_base_url() {
         assert_eq!(MatrixToUri::parse("").unwrap_err(), MatrixToError::WrongBaseUrl.into());
@@ -1055,6 +1088,18 @@ mod tests {
         assert_eq!(matrix_uri.action(), Some(&UriAction::Join));
     }
 
+    #[test]
+    fn matrixuri_custom_action_round_trip() {
+        let uri = "matrix:roomid/ruma:notareal.hs?via=notareal.hs&via=anotherunreal.hs&action=custom%26action";
+        let matrix_uri = MatrixUri::parse(uri).expect("Failed to create MatrixUri.");
+        assert_eq!(
+            matrix_uri.via(),
+            &[server_name!("notareal.hs").to_owned(), server_name!("anotherunreal.hs").to_owned()]
+        );
+        assert_eq!(matrix_uri.action().unwrap().as_str(), "custom&action");
+        assert_eq!(matrix_uri.to_string(), uri);
+    }
+
     #[test]
     fn parse_matrixuri_invalid_uri() {
         assert_eq!(
@@ -40,6 +40,33 @@ impl MxcUri {
         })
     }
 
+    /// Returns the server name part of this MXC URI as a string slice, without validating it.
+    ///
+    /// Unlike [`server_name()`](Self::server_name), this can be used in a `const` context, for
+    /// example with an URI created by the `mxc_uri!` macro, which is validated at compile time.
+    /// Since the server name is not validated, it is not returned as a [`ServerName`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the URI doesn't start with `mxc://` or doesn't contain a slash after the server
+    /// name.
+    pub const fn server_name_str(&self) -> &str {
+        self.parts_str().0
+    }
+
+    /// Returns the media ID part of this MXC URI, without validating it.
+    ///
+    /// Unlike [`media_id()`](Self::media_id), this can be used in a `const` context, for example
+    /// with an URI created by the `mxc_uri!` macro, which is validated at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the URI doesn't start with `mxc://` or doesn't contain a slash after the server
+    /// name.
+    pub const fn media_id_str(&self) -> &str {
+        self.parts_str().1
+    }
+
     /// Validates the URI and returns an error if it failed.
     pub fn validate(&self) -> Result<()> {
         self.extract_slash_idx().map(|_| ())
@@ -51,6 +78,38 @@ impl MxcUri {
         self.validate().is_ok()
     }
 
+    #[doc(hidden)]
+    pub const fn from_static(s: &'static str) -> &'static Self {
+        Self::from_borrowed(s)
+    }
+
+    const fn parts_str(&self) -> (&str, &str) {
+        const PROTOCOL: &[u8] = b"mxc://";
+
+        let bytes = self.0.as_bytes();
+        assert!(bytes.len() > PROTOCOL.len(), "MXC URI doesn't start with `mxc://`");
+
+        let mut i = 0;
+        while i < PROTOCOL.len() {
+            assert!(bytes[i] == PROTOCOL[i], "MXC URI doesn't start with `mxc://`");
+            i += 1;
+        }
+
+        while i < bytes.len() && bytes[i] != b'/' {
+            i += 1;
+        }
+        assert!(i < bytes.len(), "MXC URI doesn't contain a slash after the server name");
+
+        let (_, rest) = bytes.split_at(PROTOCOL.len());
+        let (server_name, media_id) = rest.split_at(i - PROTOCOL.len());
+        let (_, media_id) = media_id.split_at(1);
+
+        // SAFETY: Both slices come from a `str` and were split around ASCII characters.
+        unsafe {
+            (std::str::from_utf8_unchecked(server_name), std::str::from_utf8_unchecked(media_id))
+        }
+    }
+
     // convenience method for calling validate(self)
     #[inline(always)]
     fn extract_slash_idx(&self) -> Result<NonZeroU8> {
@@ -63,6 +122,7 @@ mod tests {
     use ruma_identifiers_validation::error::MxcUriError;
 
     use super::{MxcUri, OwnedMxcUri};
+    use crate::server_name;
 
     #[test]
     fn parse_mxc_uri() {
@@ -75,6 +135,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parts_in_const_context() {
+        const MXC: &MxcUri = crate::mxc_uri!("mxc://example.com/AQwafuaFswefuhsfAFAgsw");
+        const SERVER_NAME: &str = MXC.server_name_str();
+        const MEDIA_ID: &str = MXC.media_id_str();
+
+        assert_eq!(SERVER_NAME, "example.com");
+        assert_eq!(MEDIA_ID, "AQwafuaFswefuhsfAFAgsw");
+        assert_eq!(MXC.parts(), Ok((server_name!("example.com"), MEDIA_ID)));
+    }
+
+    #[test]
+    fn parts_str_with_invalid_server_name() {
+        let mxc = <&MxcUri>::from("mxc://not a host/x");
+
+        assert_eq!(mxc.server_name_str(), "not a host");
+        assert_eq!(mxc.media_id_str(), "x");
+        // The invalid server name is never returned as a `ServerName`.
+        assert_eq!(mxc.server_name(), Err(MxcUriError::ServerNameMalformed));
+    }
+
+    #[test]
+    #[should_panic = "doesn't contain a slash"]
+    fn media_id_str_without_slash() {
+        let mxc = <&MxcUri>::from("mxc://127.0.0.1");
+        mxc.media_id_str();
+    }
+
     #[test]
     fn parse_mxc_uri_without_media_id() {
         let mxc = Box::<MxcUri>::from("mxc://127.0.0.1");
@@ -59,6 +59,16 @@ impl RoomOrAliasId {
         self.variant() == Variant::RoomAliasId
     }
 
+    /// Returns this ID as a room ID, if it is one.
+    pub fn as_room_id(&self) -> Option<&RoomId> {
+        <&RoomId>::try_from(self).ok()
+    }
+
+    /// Returns this ID as a room alias ID, if it is one.
+    pub fn as_room_alias_id(&self) -> Option<&RoomAliasId> {
+        <&RoomAliasId>::try_from(self).ok()
+    }
+
     fn variant(&self) -> Variant {
         match self.as_bytes().first() {
             Some(b'!') => Variant::RoomId,
@@ -68,6 +78,38 @@ impl RoomOrAliasId {
     }
 }
 
+impl OwnedRoomOrAliasId {
+    /// Converts this ID into either an owned room ID or an owned room alias ID.
+    pub fn into_either(self) -> RoomIdOrAliasId {
+        match OwnedRoomId::try_from(self) {
+            Ok(room_id) => RoomIdOrAliasId::RoomId(room_id),
+            Err(room_alias_id) => RoomIdOrAliasId::RoomAliasId(room_alias_id),
+        }
+    }
+}
+
+/// Either an owned room ID or an owned room alias ID.
+///
+/// This is returned by [`OwnedRoomOrAliasId::into_either`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::exhaustive_enums)]
+pub enum RoomIdOrAliasId {
+    /// A room ID.
+    RoomId(OwnedRoomId),
+
+    /// A room alias ID.
+    RoomAliasId(OwnedRoomAliasId),
+}
+
+impl From<RoomIdOrAliasId> for OwnedRoomOrAliasId {
+    fn from(id: RoomIdOrAliasId) -> Self {
+        match id {
+            RoomIdOrAliasId::RoomId(room_id) => room_id.into(),
+            RoomIdOrAliasId::RoomAliasId(room_alias_id) => room_alias_id.into(),
+        }
+    }
+}
+
 #[derive(PartialEq, Eq)]
 enum Variant {
     RoomId,
@@ -148,8 +190,8 @@ impl TryFrom<OwnedRoomOrAliasId> for OwnedRoomAliasId {
 
 #[cfg(test)]
 mod tests {
-    use super::{OwnedRoomOrAliasId, RoomOrAliasId};
-    use crate::IdParseError;
+    use super::{OwnedRoomOrAliasId, RoomIdOrAliasId, RoomOrAliasId};
+    use crate::{owned_room_alias_id, owned_room_id, IdParseError};
 
     #[test]
     fn valid_room_id_or_alias_id_with_a_room_alias_id() {
@@ -221,4 +263,29 @@ mod tests {
                 .expect("Failed to create RoomAliasId.")
         );
     }
+
+    #[test]
+    fn as_typed_ids() {
+        let room_id = <&RoomOrAliasId>::try_from("!29fhd83h92h0:example.com").unwrap();
+        assert_eq!(room_id.as_room_id().unwrap(), "!29fhd83h92h0:example.com");
+        assert_eq!(room_id.as_room_alias_id(), None);
+
+        let alias_id = <&RoomOrAliasId>::try_from("#ruma:example.com").unwrap();
+        assert_eq!(alias_id.as_room_id(), None);
+        assert_eq!(alias_id.as_room_alias_id().unwrap(), "#ruma:example.com");
+    }
+
+    #[test]
+    fn into_either() {
+        let room_id = OwnedRoomOrAliasId::try_from("!29fhd83h92h0:example.com").unwrap();
+        assert_eq!(
+            room_id.into_either(),
+            RoomIdOrAliasId::RoomId(owned_room_id!("!29fhd83h92h0:example.com"))
+        );
+
+        let alias_id = OwnedRoomOrAliasId::try_from("#ruma:example.com").unwrap();
+        let either = alias_id.clone().into_either();
+        assert_eq!(either, RoomIdOrAliasId::RoomAliasId(owned_room_alias_id!("#ruma:example.com")));
+        assert_eq!(OwnedRoomOrAliasId::from(either), alias_id);
+    }
 }
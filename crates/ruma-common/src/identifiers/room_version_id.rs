@@ -88,6 +88,38 @@ impl RoomVersionId {
     pub fn as_bytes(&self) -> &[u8] {
         self.as_str().as_bytes()
     }
+
+    /// Whether rooms of this version use event IDs computed from the reference hash of the event,
+    /// instead of IDs of the form `$localpart:server_name`.
+    ///
+    /// This was introduced in room version 3. Returns `false` for custom room versions.
+    pub fn uses_reference_hash_event_ids(&self) -> bool {
+        !matches!(self, Self::V1 | Self::V2 | Self::_Custom(_))
+    }
+
+    /// Whether rooms of this version support the `knock` join rule and membership.
+    ///
+    /// This was introduced in room version 7. Returns `false` for custom room versions.
+    pub fn supports_knocking(&self) -> bool {
+        !matches!(
+            self,
+            Self::V1 | Self::V2 | Self::V3 | Self::V4 | Self::V5 | Self::V6 | Self::_Custom(_)
+        )
+    }
+
+    /// Whether rooms of this version support the `restricted` join rule.
+    ///
+    /// This was introduced in room version 8. Returns `false` for custom room versions.
+    pub fn supports_restricted_join_rules(&self) -> bool {
+        self.supports_knocking() && !matches!(self, Self::V7)
+    }
+
+    /// Whether rooms of this version support the `knock_restricted` join rule.
+    ///
+    /// This was introduced in room version 10. Returns `false` for custom room versions.
+    pub fn supports_knock_restricted_join_rule(&self) -> bool {
+        self.supports_restricted_join_rules() && !matches!(self, Self::V8 | Self::V9)
+    }
 }
 
 impl From<RoomVersionId> for String {
@@ -267,6 +299,42 @@ mod tests {
     use super::RoomVersionId;
     use crate::IdParseError;
 
+    #[test]
+    fn capabilities() {
+        let versions = [
+            RoomVersionId::V1,
+            RoomVersionId::V2,
+            RoomVersionId::V3,
+            RoomVersionId::V4,
+            RoomVersionId::V5,
+            RoomVersionId::V6,
+            RoomVersionId::V7,
+            RoomVersionId::V8,
+            RoomVersionId::V9,
+            RoomVersionId::V10,
+            RoomVersionId::V11,
+        ];
+        let first_with = |capability: fn(&RoomVersionId) -> bool| {
+            let idx = versions.iter().position(capability).unwrap();
+            assert!(versions[idx..].iter().all(capability));
+            &versions[idx]
+        };
+
+        assert_eq!(first_with(RoomVersionId::uses_reference_hash_event_ids), &RoomVersionId::V3);
+        assert_eq!(first_with(RoomVersionId::supports_knocking), &RoomVersionId::V7);
+        assert_eq!(first_with(RoomVersionId::supports_restricted_join_rules), &RoomVersionId::V8);
+        assert_eq!(
+            first_with(RoomVersionId::supports_knock_restricted_join_rule),
+            &RoomVersionId::V10
+        );
+
+        let custom = RoomVersionId::try_from("io.ruma.custom").unwrap();
+        assert!(!custom.uses_reference_hash_event_ids());
+        assert!(!custom.supports_knocking());
+        assert!(!custom.supports_restricted_join_rules());
+        assert!(!custom.supports_knock_restricted_join_rule());
+    }
+
     #[test]
     fn valid_version_1_room_version_id() {
         assert_eq!(
@@ -51,9 +51,90 @@ impl ServerName {
     }
 }
 
+/// The result of resolving a server name to the server to connect to.
+///
+/// This models the outcome of the [server discovery algorithm] of the Server-Server API.
+///
+/// [server discovery algorithm]: https://spec.matrix.org/latest/server-server-api/#resolving-server-names
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ResolvedServerName {
+    /// The host to connect to.
+    ///
+    /// This is either an IP literal or a hostname that should be resolved with `AAAA` or `A`
+    /// records.
+    pub host: String,
+
+    /// The port to connect to.
+    pub port: u16,
+
+    /// The authority to use in the `Host` header of the requests.
+    ///
+    /// This is also the name that the TLS certificate of the server must be valid for.
+    pub authority_for_host_header: String,
+
+    /// Whether the server name was delegated with `/.well-known/matrix/server`.
+    pub use_well_known: bool,
+}
+
+impl ResolvedServerName {
+    /// The default port of the Server-Server API.
+    pub const DEFAULT_PORT: u16 = 8448;
+
+    /// Creates a new `ResolvedServerName` to connect directly to the given server name.
+    ///
+    /// This is the result of the algorithm when the server name is an IP literal, has an explicit
+    /// port, or when no delegation or SRV record was found.
+    pub fn direct(server_name: &ServerName) -> Self {
+        Self {
+            host: server_name.host().to_owned(),
+            port: server_name.port().unwrap_or(Self::DEFAULT_PORT),
+            authority_for_host_header: server_name.as_str().to_owned(),
+            use_well_known: false,
+        }
+    }
+
+    /// Creates a new `ResolvedServerName` to connect to the delegated server name returned by
+    /// `/.well-known/matrix/server`.
+    ///
+    /// This is the result of the algorithm when the delegated server name is an IP literal, has
+    /// an explicit port, or when no SRV record was found for it.
+    pub fn well_known(delegated_server_name: &ServerName) -> Self {
+        Self { use_well_known: true, ..Self::direct(delegated_server_name) }
+    }
+
+    /// Creates a new `ResolvedServerName` to connect to the target of an SRV record.
+    ///
+    /// `server_name` is the server name the SRV record was looked up for, which is the delegated
+    /// server name if `use_well_known` is `true`. It is still used for the `Host` header.
+    pub fn srv(
+        server_name: &ServerName,
+        target: impl Into<String>,
+        port: u16,
+        use_well_known: bool,
+    ) -> Self {
+        Self {
+            host: target.into(),
+            port,
+            authority_for_host_header: server_name.as_str().to_owned(),
+            use_well_known,
+        }
+    }
+
+    /// The value of the `Host` header to use in the requests to this server.
+    pub fn host_header(&self) -> &str {
+        &self.authority_for_host_header
+    }
+
+    /// The `host:port` authority to connect to.
+    pub fn connect_authority(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ServerName;
+    use super::{ResolvedServerName, ServerName};
 
     #[test]
     fn ipv4_host() {
@@ -116,6 +197,7 @@ mod tests {
         let server_name = <&ServerName>::try_from("127.0.0.1").unwrap();
         assert!(server_name.is_ip_literal());
         assert_eq!(server_name.host(), "127.0.0.1");
+        assert_eq!(server_name.port(), None);
     }
 
     #[test]
@@ -123,6 +205,7 @@ mod tests {
         let server_name = <&ServerName>::try_from("1.1.1.1:12000").unwrap();
         assert!(server_name.is_ip_literal());
         assert_eq!(server_name.host(), "1.1.1.1");
+        assert_eq!(server_name.port(), Some(12000));
     }
 
     #[test]
@@ -130,6 +213,7 @@ mod tests {
         let server_name = <&ServerName>::try_from("[::1]").unwrap();
         assert!(server_name.is_ip_literal());
         assert_eq!(server_name.host(), "[::1]");
+        assert_eq!(server_name.port(), None);
     }
 
     #[test]
@@ -137,6 +221,15 @@ mod tests {
         let server_name = <&ServerName>::try_from("[1234:5678::abcd]:5678").unwrap();
         assert!(server_name.is_ip_literal());
         assert_eq!(server_name.host(), "[1234:5678::abcd]");
+        assert_eq!(server_name.port(), Some(5678));
+    }
+
+    #[test]
+    fn parse_ipv6_loopback_with_port() {
+        let server_name = <&ServerName>::try_from("[::1]:8448").unwrap();
+        assert!(server_name.is_ip_literal());
+        assert_eq!(server_name.host(), "[::1]");
+        assert_eq!(server_name.port(), Some(8448));
     }
 
     #[test]
@@ -144,6 +237,7 @@ mod tests {
         let server_name = <&ServerName>::try_from("example.com").unwrap();
         assert!(!server_name.is_ip_literal());
         assert_eq!(server_name.host(), "example.com");
+        assert_eq!(server_name.port(), None);
     }
 
     #[test]
@@ -151,5 +245,42 @@ mod tests {
         let server_name = <&ServerName>::try_from("ruma.io:8080").unwrap();
         assert!(!server_name.is_ip_literal());
         assert_eq!(server_name.host(), "ruma.io");
+        assert_eq!(server_name.port(), Some(8080));
+    }
+
+    #[test]
+    fn resolved_direct_ip() {
+        let server_name = <&ServerName>::try_from("[::1]:8000").unwrap();
+        let resolved = ResolvedServerName::direct(server_name);
+
+        assert_eq!(resolved.host, "[::1]");
+        assert_eq!(resolved.port, 8000);
+        assert_eq!(resolved.host_header(), "[::1]:8000");
+        assert_eq!(resolved.connect_authority(), "[::1]:8000");
+        assert!(!resolved.use_well_known);
+    }
+
+    #[test]
+    fn resolved_well_known() {
+        let delegated = <&ServerName>::try_from("matrix.example.org").unwrap();
+        let resolved = ResolvedServerName::well_known(delegated);
+
+        assert_eq!(resolved.host, "matrix.example.org");
+        assert_eq!(resolved.port, ResolvedServerName::DEFAULT_PORT);
+        assert_eq!(resolved.host_header(), "matrix.example.org");
+        assert_eq!(resolved.connect_authority(), "matrix.example.org:8448");
+        assert!(resolved.use_well_known);
+    }
+
+    #[test]
+    fn resolved_srv() {
+        let server_name = <&ServerName>::try_from("example.org").unwrap();
+        let resolved = ResolvedServerName::srv(server_name, "federation.example.net", 443, false);
+
+        assert_eq!(resolved.host, "federation.example.net");
+        assert_eq!(resolved.port, 443);
+        assert_eq!(resolved.host_header(), "example.org");
+        assert_eq!(resolved.connect_authority(), "federation.example.net:443");
+        assert!(!resolved.use_well_known);
     }
 }
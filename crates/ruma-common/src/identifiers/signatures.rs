@@ -2,7 +2,10 @@ use std::{borrow::Borrow, collections::BTreeMap};
 
 use serde::{Deserialize, Serialize};
 
-use super::{OwnedDeviceId, OwnedKeyName, OwnedServerName, OwnedSigningKeyId, OwnedUserId};
+use super::{
+    OwnedDeviceId, OwnedKeyName, OwnedServerName, OwnedSigningKeyId, OwnedUserId,
+    SigningKeyAlgorithm, SigningKeyId,
+};
 
 /// Map of key identifier to signature values.
 pub type EntitySignatures<K> = BTreeMap<OwnedSigningKeyId<K>, String>;
@@ -40,6 +43,36 @@ impl<E: Ord, K: ?Sized> Signatures<E, K> {
         self.0.entry(entity).or_default().insert(key_identifier, value)
     }
 
+    /// Adds all the signatures of `other` to this map.
+    ///
+    /// If a signature already exists for the same entity and key identifier, it is replaced by the
+    /// one from `other`. Use [`merge_with`](Self::merge_with) to choose which one is kept.
+    pub fn merge(&mut self, other: Signatures<E, K>) {
+        self.merge_with(other, |_, _, new| new);
+    }
+
+    /// Adds all the signatures of `other` to this map, calling `resolve` to get the signature to
+    /// keep when one already exists for the same entity and key identifier.
+    ///
+    /// `resolve` is called with the key identifier, the existing signature and the signature from
+    /// `other`, in that order.
+    pub fn merge_with<F>(&mut self, other: Signatures<E, K>, mut resolve: F)
+    where
+        F: FnMut(&OwnedSigningKeyId<K>, String, String) -> String,
+    {
+        for (entity, signatures) in other.0 {
+            let entity_signatures = self.0.entry(entity).or_default();
+
+            for (key_id, signature) in signatures {
+                let signature = match entity_signatures.remove(&key_id) {
+                    Some(existing) => resolve(&key_id, existing, signature),
+                    None => signature,
+                };
+                entity_signatures.insert(key_id, signature);
+            }
+        }
+    }
+
     /// Returns a reference to the signatures corresponding to the entities.
     pub fn get<Q>(&self, entity: &Q) -> Option<&EntitySignatures<K>>
     where
@@ -48,6 +81,50 @@ impl<E: Ord, K: ?Sized> Signatures<E, K> {
     {
         self.0.get(entity)
     }
+
+    /// Returns the signature of the given entity with the given key, if any.
+    pub fn get_signature<Q>(&self, entity: &Q, key_id: &SigningKeyId<K>) -> Option<&str>
+    where
+        E: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.0.get(entity)?.get(key_id).map(String::as_str)
+    }
+
+    /// Iterates over all the signatures, as `(entity, key_identifier, signature)` triples.
+    pub fn iter_signatures(&self) -> impl Iterator<Item = (&E, &OwnedSigningKeyId<K>, &str)> {
+        self.0.iter().flat_map(|(entity, signatures)| {
+            signatures.iter().map(move |(key_id, signature)| (entity, key_id, signature.as_str()))
+        })
+    }
+
+    /// Iterates over the signatures made with a key using the given algorithm, as
+    /// `(entity, key_identifier, signature)` triples.
+    pub fn filter_by_algorithm(
+        &self,
+        algorithm: SigningKeyAlgorithm,
+    ) -> impl Iterator<Item = (&E, &OwnedSigningKeyId<K>, &str)> {
+        self.iter_signatures().filter(move |(_, key_id, _)| key_id.algorithm() == algorithm)
+    }
+
+    /// Whether there are signatures for the given entity.
+    pub fn contains_entity<Q>(&self, entity: &Q) -> bool
+    where
+        E: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.0.contains_key(entity)
+    }
+}
+
+impl<E: Ord, K: ?Sized> FromIterator<(E, OwnedSigningKeyId<K>, String)> for Signatures<E, K> {
+    fn from_iter<T: IntoIterator<Item = (E, OwnedSigningKeyId<K>, String)>>(iter: T) -> Self {
+        let mut signatures = Self::new();
+        for (entity, key_identifier, value) in iter {
+            signatures.insert(entity, key_identifier, value);
+        }
+        signatures
+    }
 }
 
 /// Map of server signatures for an event, grouped by server.
@@ -55,3 +132,104 @@ pub type ServerSignatures = Signatures<OwnedServerName, OwnedKeyName>;
 
 /// Map of device signatures for an event, grouped by user.
 pub type DeviceSignatures = Signatures<OwnedUserId, OwnedDeviceId>;
+
+#[cfg(test)]
+mod tests {
+    use super::Signatures;
+    use crate::{server_name, KeyId, OwnedServerName, SigningKeyAlgorithm};
+
+    #[test]
+    fn get_signature() {
+        let key_id = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "1");
+        let other_key_id = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "2");
+        let mut signatures = Signatures::<OwnedServerName, str>::new();
+        signatures.insert(server_name!("example.org").to_owned(), key_id.clone(), "sig".to_owned());
+
+        assert!(signatures.contains_entity(server_name!("example.org")));
+        assert!(!signatures.contains_entity(server_name!("other.org")));
+
+        assert_eq!(signatures.get_signature(server_name!("example.org"), &key_id), Some("sig"));
+        assert_eq!(signatures.get_signature(server_name!("example.org"), &other_key_id), None);
+        assert_eq!(signatures.get_signature(server_name!("other.org"), &key_id), None);
+    }
+
+    #[test]
+    fn merge() {
+        let key_1 = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "1");
+        let key_2 = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "2");
+        let example = server_name!("example.org");
+        let other = server_name!("other.org");
+
+        let existing_signatures = || {
+            let mut signatures = Signatures::<OwnedServerName, str>::new();
+            signatures.insert(example.to_owned(), key_1.clone(), "old".to_owned());
+            signatures
+        };
+        let new_signatures = || {
+            let mut signatures = Signatures::<OwnedServerName, str>::new();
+            signatures.insert(example.to_owned(), key_1.clone(), "new".to_owned());
+            signatures.insert(example.to_owned(), key_2.clone(), "sig_2".to_owned());
+            signatures.insert(other.to_owned(), key_1.clone(), "other".to_owned());
+            signatures
+        };
+
+        let mut signatures = existing_signatures();
+        signatures.merge(new_signatures());
+        assert_eq!(signatures.get_signature(example, &key_1), Some("new"));
+        assert_eq!(signatures.get_signature(example, &key_2), Some("sig_2"));
+        assert_eq!(signatures.get_signature(other, &key_1), Some("other"));
+
+        let mut signatures = existing_signatures();
+        signatures.merge_with(new_signatures(), |_, existing, _| existing);
+        assert_eq!(signatures.get_signature(example, &key_1), Some("old"));
+        assert_eq!(signatures.get_signature(example, &key_2), Some("sig_2"));
+        assert_eq!(signatures.get_signature(other, &key_1), Some("other"));
+    }
+
+    #[test]
+    fn iter_signatures() {
+        let key_1 = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "1");
+        let key_2 = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "2");
+        let example = server_name!("example.org");
+        let other = server_name!("other.org");
+
+        let signatures: Signatures<OwnedServerName, str> = [
+            (other.to_owned(), key_1.clone(), "sig_3".to_owned()),
+            (example.to_owned(), key_2.clone(), "sig_2".to_owned()),
+            (example.to_owned(), key_1.clone(), "sig_1".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+
+        let triples = signatures
+            .iter_signatures()
+            .map(|(entity, key_id, signature)| (entity.as_str(), key_id.as_str(), signature))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            triples,
+            [
+                ("example.org", "ed25519:1", "sig_1"),
+                ("example.org", "ed25519:2", "sig_2"),
+                ("other.org", "ed25519:1", "sig_3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn filter_by_algorithm() {
+        let ed25519_key = KeyId::from_parts(SigningKeyAlgorithm::Ed25519, "1");
+        let custom_key = KeyId::from_parts(SigningKeyAlgorithm::from("custom"), "1");
+        let example = server_name!("example.org");
+
+        let signatures: Signatures<OwnedServerName, str> = [
+            (example.to_owned(), ed25519_key.clone(), "ed25519_sig".to_owned()),
+            (example.to_owned(), custom_key.clone(), "custom_sig".to_owned()),
+        ]
+        .into_iter()
+        .collect();
+
+        let ed25519_signatures =
+            signatures.filter_by_algorithm(SigningKeyAlgorithm::Ed25519).collect::<Vec<_>>();
+        assert_eq!(ed25519_signatures, [(&example.to_owned(), &ed25519_key, "ed25519_sig")]);
+    }
+}
@@ -2,7 +2,9 @@
 
 use std::{rc::Rc, sync::Arc};
 
-use super::{matrix_uri::UriAction, IdParseError, MatrixToUri, MatrixUri, ServerName};
+use super::{
+    matrix_uri::UriAction, IdParseError, MatrixToUri, MatrixUri, OwnedServerName, ServerName,
+};
 
 /// A Matrix [user ID].
 ///
@@ -99,6 +101,14 @@ impl UserId {
         ServerName::from_borrowed(&self.as_str()[self.colon_idx() + 1..])
     }
 
+    /// Returns the localpart and the server name of the user ID.
+    ///
+    /// The parts are not validated again, so this also works for historical user IDs.
+    pub fn parts(&self) -> (&str, &ServerName) {
+        let (localpart, server_name) = self.as_str()[1..].split_at(self.colon_idx() - 1);
+        (localpart, ServerName::from_borrowed(&server_name[1..]))
+    }
+
     /// Whether this user ID is a historical one.
     ///
     /// A historical user ID is one that doesn't conform to the latest specification of the user ID
@@ -124,6 +134,33 @@ impl UserId {
         MatrixToUri::new(self.into(), Vec::new())
     }
 
+    /// Create a `matrix.to` URI for this user ID with a list of servers that can be used to find
+    /// the user.
+    ///
+    /// This is useful when the homeserver of the user might not be reachable.
+    ///
+    /// If you don't have a list of servers, you can use [`UserId::matrix_to_uri()`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ruma_common::{server_name, user_id};
+    ///
+    /// assert_eq!(
+    ///     user_id!("@jplatte:notareal.hs")
+    ///         .matrix_to_uri_via([&*server_name!("example.org")])
+    ///         .to_string(),
+    ///     "https://matrix.to/#/@jplatte:notareal.hs?via=example.org"
+    /// );
+    /// ```
+    pub fn matrix_to_uri_via<T>(&self, via: T) -> MatrixToUri
+    where
+        T: IntoIterator,
+        T::Item: Into<OwnedServerName>,
+    {
+        MatrixToUri::new(self.into(), via.into_iter().map(Into::into).collect())
+    }
+
     /// Create a `matrix:` URI for this user ID.
     ///
     /// If `chat` is `true`, a click on the URI should start a direct message
@@ -163,7 +200,12 @@ mod tests {
         assert_eq!(user_id.as_str(), "@carl:example.com");
         assert_eq!(user_id.localpart(), "carl");
         assert_eq!(user_id.server_name(), "example.com");
+        assert_eq!(user_id.parts(), ("carl", server_name!("example.com")));
         assert!(!user_id.is_historical());
+
+        let user_id = <&UserId>::try_from("@Carl:example.com:8448").unwrap();
+        assert_eq!(user_id.parts(), ("Carl", server_name!("example.com:8448")));
+        assert!(user_id.is_historical());
     }
 
     #[test]
@@ -218,6 +260,7 @@ mod tests {
         assert_eq!(user_id.as_str(), "@a%b[irc]:example.com");
         assert_eq!(user_id.localpart(), "a%b[irc]");
         assert_eq!(user_id.server_name(), "example.com");
+        assert_eq!(user_id.parts(), ("a%b[irc]", server_name!("example.com")));
         assert!(user_id.is_historical());
     }
 
@@ -23,9 +23,10 @@ pub mod authentication;
 #[cfg(feature = "canonical-json")]
 pub mod canonical_json;
 pub mod directory;
+mod display_name;
 pub mod encryption;
 mod identifiers;
-mod percent_encode;
+pub mod percent_encode;
 pub mod power_levels;
 pub mod presence;
 pub mod push;
@@ -41,6 +42,7 @@ use std::fmt;
 #[cfg(feature = "canonical-json")]
 pub use self::canonical_json::{CanonicalJsonError, CanonicalJsonObject, CanonicalJsonValue};
 pub use self::{
+    display_name::disambiguate,
     identifiers::*,
     time::{MilliSecondsSinceUnixEpoch, SecondsSinceUnixEpoch},
 };
@@ -1,4 +1,8 @@
-use percent_encoding::{AsciiSet, CONTROLS};
+//! Percent-encoding of URL components.
+
+use std::borrow::Cow;
+
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 
 /// The [path percent-encode set] as defined in the WHATWG URL standard + `/` since
 /// we always encode single segments of the path.
@@ -15,3 +19,45 @@ pub(crate) const PATH_PERCENT_ENCODE_SET: &AsciiSet = &CONTROLS
     .add(b'{')
     .add(b'}')
     .add(b'/');
+
+/// The [component percent-encode set] as defined in the WHATWG URL standard, to encode single
+/// keys or values of a query string.
+///
+/// Contrary to the query percent-encode set, it encodes `&`, `=` and `+`, which have a special
+/// meaning in query strings.
+///
+/// [component percent-encode set]: https://url.spec.whatwg.org/#component-percent-encode-set
+pub const QUERY_PERCENT_ENCODE_SET: &AsciiSet = &PATH_PERCENT_ENCODE_SET
+    .add(b'$')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b',')
+    .add(b':')
+    .add(b';')
+    .add(b'=')
+    .add(b'@')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'|');
+
+/// Percent-encode a single key or value of a query string.
+///
+/// The result can be safely inserted between the `&` and `=` separators of a query string.
+pub fn encode_query_value(value: &str) -> Cow<'_, str> {
+    utf8_percent_encode(value, QUERY_PERCENT_ENCODE_SET).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_query_value;
+
+    #[test]
+    fn encode_query_value_with_separators() {
+        assert_eq!(encode_query_value("plain"), "plain");
+        assert_eq!(encode_query_value("a&b=c"), "a%26b%3Dc");
+        assert_eq!(encode_query_value("1+1 #2%"), "1%2B1%20%232%25");
+    }
+}
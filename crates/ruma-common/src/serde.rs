@@ -17,6 +17,7 @@ pub mod json_string;
 mod raw;
 pub mod single_element_seq;
 mod strings;
+mod tagged_enum;
 pub mod test;
 
 pub use self::{
@@ -30,6 +31,7 @@ pub use self::{
         deserialize_as_optional_number_or_string, deserialize_v1_powerlevel, empty_string_as_none,
         none_as_empty_string,
     },
+    tagged_enum::deserialize_tagged_enum,
 };
 
 /// The inner type of [`JsonValue::Object`].
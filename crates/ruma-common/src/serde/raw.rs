@@ -1,5 +1,6 @@
 use std::{
     clone::Clone,
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Debug},
     marker::PhantomData,
     mem,
@@ -186,6 +187,53 @@ impl<T> Raw<T> {
         serde_json::from_str(self.json.get())
     }
 
+    /// Try to deserialize the JSON as the expected type, rejecting objects with duplicate keys.
+    ///
+    /// When an object has several fields with the same name, `serde_json` keeps the last value,
+    /// which is also what happens when converting it to canonical JSON. Other implementations
+    /// might keep another value, so it is safer to reject such objects, for example when they are
+    /// received over federation.
+    ///
+    /// Only the keys of the top-level object are checked.
+    pub fn deserialize_strict<'a>(&'a self) -> serde_json::Result<T>
+    where
+        T: Deserialize<'a>,
+    {
+        struct UniqueKeysVisitor;
+
+        impl<'de> Visitor<'de> for UniqueKeysVisitor {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a JSON object")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut keys = BTreeSet::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    map.next_value::<IgnoredAny>()?;
+
+                    if let Some(key) = keys.replace(key) {
+                        return Err(de::Error::custom(format!("duplicate field `{key}`")));
+                    }
+                }
+
+                Ok(())
+            }
+        }
+
+        let json = self.json.get();
+        if json.trim_start().starts_with('{') {
+            let mut deserializer = serde_json::Deserializer::from_str(json);
+            deserializer.deserialize_map(UniqueKeysVisitor)?;
+        }
+
+        self.deserialize()
+    }
+
     /// Try to deserialize the JSON as a custom type.
     pub fn deserialize_as<'a, U>(&'a self) -> serde_json::Result<U>
     where
@@ -194,6 +242,20 @@ impl<T> Raw<T> {
         serde_json::from_str(self.json.get())
     }
 
+    /// Creates a new `Raw` by merging the fields of the given JSON object into this one.
+    ///
+    /// This is a shallow merge: the fields of `patch` replace the fields with the same name in
+    /// `self`, nested objects are not merged. The fields of the resulting object are sorted.
+    ///
+    /// Returns `Err(_)` if either `self` or `patch` is not a JSON object.
+    pub fn merge(&self, patch: &RawJsonValue) -> serde_json::Result<Self> {
+        let mut object: BTreeMap<String, &RawJsonValue> = serde_json::from_str(self.json.get())?;
+        let patch: BTreeMap<String, &RawJsonValue> = serde_json::from_str(patch.get())?;
+        object.extend(patch);
+
+        to_raw_json_value(&object).map(Self::from_json)
+    }
+
     /// Turns `Raw<T>` into `Raw<U>` without changing the underlying JSON.
     ///
     /// This is useful for turning raw specific event types into raw event enum types.
@@ -243,7 +305,11 @@ impl<T> Serialize for Raw<T> {
 #[cfg(test)]
 mod tests {
     use serde::Deserialize;
-    use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+    use serde_json::{
+        from_str as from_json_str, json,
+        value::{to_raw_value as to_raw_json_value, RawValue as RawJsonValue},
+        Value as JsonValue,
+    };
 
     use super::Raw;
 
@@ -267,4 +333,50 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn merge() -> serde_json::Result<()> {
+        let raw: Raw<()> =
+            from_json_str(r#"{ "msgtype": "m.text", "body": "Hello", "format": { "a": 1 } }"#)?;
+        let patch = to_raw_json_value(&json!({ "body": "Hello, World!", "extra": true }))?;
+
+        let merged = raw.merge(&patch)?;
+        assert_eq!(
+            merged.json().get(),
+            r#"{"body":"Hello, World!","extra":true,"format":{ "a": 1 },"msgtype":"m.text"}"#
+        );
+
+        raw.merge(&to_raw_json_value(&json!(["not", "an", "object"]))?).unwrap_err();
+
+        Ok(())
+    }
+
+    #[test]
+    fn deserialize_strict() -> serde_json::Result<()> {
+        let raw: Raw<JsonValue> = from_json_str(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@alice:example.org",
+                "content": { "body": "Hello" },
+                "sender": "@mallory:example.org"
+            }"#,
+        )?;
+
+        // Without the strict mode, the last value wins.
+        assert_eq!(raw.deserialize()?["sender"], "@mallory:example.org");
+        let err = raw.deserialize_strict().unwrap_err();
+        assert!(err.to_string().starts_with("duplicate field `sender`"), "{err}");
+
+        // Nested objects are not checked.
+        let raw: Raw<JsonValue> = from_json_str(
+            r#"{
+                "type": "m.room.message",
+                "sender": "@alice:example.org",
+                "content": { "body": "Hello", "body": "Hello again" }
+            }"#,
+        )?;
+        assert_eq!(raw.deserialize_strict()?["content"], json!({ "body": "Hello again" }));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,122 @@
+//! Helper to deserialize internally tagged enums with a fallback for unknown tags.
+
+use serde::{de, Deserialize, Deserializer};
+use serde_json::value::RawValue as RawJsonValue;
+
+use super::Raw;
+
+/// Deserialize an internally tagged enum.
+///
+/// The value of the `tag` field is read from the JSON object first, then `from_tag` is called with
+/// it and the full JSON object, to deserialize the variant matching the tag. `from_tag` should
+/// fall back to a custom variant for unknown tags, usually with [`from_raw_json_value`].
+///
+/// Returns an error if the `tag` field is missing or is not a string.
+///
+/// [`from_raw_json_value`]: super::from_raw_json_value
+///
+/// # Example
+///
+/// ```
+/// use ruma_common::serde::{deserialize_tagged_enum, from_raw_json_value, JsonObject};
+/// use serde::{Deserialize, Deserializer};
+///
+/// #[derive(Deserialize)]
+/// struct Circle {
+///     radius: u32,
+/// }
+///
+/// enum Shape {
+///     Circle(Circle),
+///     Custom(JsonObject),
+/// }
+///
+/// impl<'de> Deserialize<'de> for Shape {
+///     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+///     where
+///         D: Deserializer<'de>,
+///     {
+///         deserialize_tagged_enum(deserializer, "shape", |shape, json| {
+///             Ok(match shape {
+///                 "circle" => Self::Circle(from_raw_json_value(json)?),
+///                 _ => Self::Custom(from_raw_json_value(json)?),
+///             })
+///         })
+///     }
+/// }
+/// ```
+pub fn deserialize_tagged_enum<'de, D, T>(
+    deserializer: D,
+    tag: &'static str,
+    from_tag: impl FnOnce(&str, &RawJsonValue) -> serde_json::Result<T>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = Raw::<()>::from_json(Box::<RawJsonValue>::deserialize(deserializer)?);
+    let tag_value = raw
+        .get_field::<String>(tag)
+        .map_err(de::Error::custom)?
+        .ok_or_else(|| de::Error::missing_field(tag))?;
+
+    from_tag(&tag_value, raw.json()).map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Deserializer};
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::deserialize_tagged_enum;
+    use crate::serde::{from_raw_json_value, JsonObject};
+
+    #[derive(Debug, Deserialize)]
+    struct Circle {
+        radius: u32,
+    }
+
+    #[derive(Debug)]
+    enum Shape {
+        Circle(Circle),
+        Custom(JsonObject),
+    }
+
+    impl<'de> Deserialize<'de> for Shape {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_tagged_enum(deserializer, "shape", |shape, json| {
+                Ok(match shape {
+                    "circle" => Self::Circle(from_raw_json_value(json)?),
+                    _ => Self::Custom(from_raw_json_value(json)?),
+                })
+            })
+        }
+    }
+
+    #[test]
+    fn known_tag() {
+        let shape = from_json_value::<Shape>(json!({ "shape": "circle", "radius": 5 })).unwrap();
+        let Shape::Circle(circle) = shape else { panic!("unexpected shape: {shape:?}") };
+        assert_eq!(circle.radius, 5);
+
+        from_json_value::<Shape>(json!({ "shape": "circle" })).unwrap_err();
+    }
+
+    #[test]
+    fn unknown_tag() {
+        let shape = from_json_value::<Shape>(json!({ "shape": "square", "side": 2 })).unwrap();
+        let Shape::Custom(object) = shape else { panic!("unexpected shape: {shape:?}") };
+        assert_eq!(object.get("side"), Some(&json!(2)));
+    }
+
+    #[test]
+    fn missing_or_invalid_tag() {
+        let err = from_json_value::<Shape>(json!({ "radius": 5 })).unwrap_err();
+        assert_eq!(err.to_string(), "missing field `shape`");
+
+        from_json_value::<Shape>(json!({ "shape": 1, "radius": 5 })).unwrap_err();
+        from_json_value::<Shape>(json!("circle")).unwrap_err();
+    }
+}
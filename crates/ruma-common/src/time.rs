@@ -43,6 +43,16 @@ impl MilliSecondsSinceUnixEpoch {
     pub fn as_secs(&self) -> UInt {
         self.0 / uint!(1000)
     }
+
+    /// Caps this timestamp so that it is at most `max_future` after `now`.
+    ///
+    /// This can be used to sort events for display, so that an event with a timestamp far in the
+    /// future doesn't stay at the top of the timeline.
+    pub fn clamp_to(self, now: Self, max_future: Duration) -> Self {
+        let max_future = UInt::try_from(max_future.as_millis()).unwrap_or(UInt::MAX);
+        let max = now.0.checked_add(max_future).unwrap_or(UInt::MAX);
+        Self(self.0.min(max))
+    }
 }
 
 impl fmt::Debug for MilliSecondsSinceUnixEpoch {
@@ -143,7 +153,7 @@ fn f64_to_uint(val: f64) -> UInt {
 mod tests {
     use std::time::{Duration, UNIX_EPOCH};
 
-    use js_int::uint;
+    use js_int::{uint, UInt};
     use serde::{Deserialize, Serialize};
     use serde_json::json;
 
@@ -175,6 +185,26 @@ mod tests {
         assert_eq!(serde_json::to_value(request).unwrap(), json!({ "millis": 2000, "secs": 0 }));
     }
 
+    #[test]
+    fn clamp_to() {
+        let now = MilliSecondsSinceUnixEpoch(uint!(1_000_000));
+        let max_future = Duration::from_secs(60);
+
+        let reasonable = MilliSecondsSinceUnixEpoch(uint!(1_030_000));
+        assert_eq!(reasonable.clamp_to(now, max_future), reasonable);
+
+        let past = MilliSecondsSinceUnixEpoch(uint!(10));
+        assert_eq!(past.clamp_to(now, max_future), past);
+
+        let far_future = MilliSecondsSinceUnixEpoch(UInt::new_wrapping(1_000_000_000_000));
+        assert_eq!(
+            far_future.clamp_to(now, max_future),
+            MilliSecondsSinceUnixEpoch(uint!(1_060_000))
+        );
+
+        assert_eq!(far_future.clamp_to(now, Duration::MAX), far_future);
+    }
+
     #[test]
     fn debug_s() {
         let seconds = SecondsSinceUnixEpoch(uint!(0));
@@ -0,0 +1,53 @@
+#![allow(clippy::exhaustive_structs)]
+
+use http::header::CONTENT_TYPE;
+use ruma_common::{
+    api::{
+        request, response, IncomingRequest as _, MatrixVersion, Metadata, OutgoingRequest as _,
+        SendAccessToken,
+    },
+    metadata,
+};
+
+const METADATA: Metadata = metadata! {
+    method: POST,
+    rate_limited: false,
+    authentication: None,
+    body_format: FormUrlEncoded,
+    history: {
+        unstable => "/_matrix/legacy/login",
+    }
+};
+
+/// Request type for the `body_format` endpoint.
+#[request]
+pub struct Request {
+    pub user: String,
+    pub password: String,
+    pub device_name: Option<String>,
+}
+
+/// Response type for the `body_format` endpoint.
+#[response]
+pub struct Response {}
+
+#[test]
+fn form_encoded_request_body() {
+    let req =
+        Request { user: "alice".to_owned(), password: "p&ss w=rd".to_owned(), device_name: None };
+    let http_req = req
+        .try_into_http_request::<Vec<u8>>(
+            "https://homeserver.tld",
+            SendAccessToken::None,
+            &[MatrixVersion::V1_1],
+        )
+        .unwrap();
+
+    assert_eq!(http_req.headers().get(CONTENT_TYPE).unwrap(), "application/x-www-form-urlencoded");
+    assert_eq!(http_req.body(), b"user=alice&password=p%26ss+w%3Drd");
+
+    let req = Request::try_from_http_request(http_req, &[] as &[String]).unwrap();
+    assert_eq!(req.user, "alice");
+    assert_eq!(req.password, "p&ss w=rd");
+    assert_eq!(req.device_name, None);
+}
@@ -198,3 +198,120 @@ mod without_query {
         assert_eq!(query, "user_id=%40_virtual_%3Aruma.io");
     }
 }
+
+mod appservice_token {
+    use http::header::AUTHORIZATION;
+    use ruma_common::{
+        api::{
+            request, response, MatrixVersion, Metadata, OutgoingRequest as _,
+            OutgoingRequestAppserviceExt, SendAccessToken,
+        },
+        metadata, user_id,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: AppserviceToken,
+        history: {
+            unstable => "/_matrix/foo/appservice",
+        }
+    };
+
+    /// Request type for the `appservice_endpoint` endpoint.
+    #[request]
+    pub struct Request {}
+
+    /// Response type for the `appservice_endpoint` endpoint.
+    #[response]
+    pub struct Response {}
+
+    #[test]
+    fn request_with_user_id_and_appservice_token() {
+        let user_id = user_id!("@_virtual_:ruma.io");
+        let http_req = Request {}
+            .try_into_http_request_with_user_id::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::Appservice("as_token"),
+                user_id,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+        assert_eq!(http_req.uri().query(), Some("user_id=%40_virtual_%3Aruma.io"));
+        assert_eq!(http_req.headers().get(AUTHORIZATION).unwrap(), "Bearer as_token");
+    }
+
+    #[test]
+    fn request_without_user_id() {
+        let http_req = Request {}
+            .try_into_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::Appservice("as_token"),
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+        assert_eq!(http_req.uri().query(), None);
+        assert_eq!(http_req.headers().get(AUTHORIZATION).unwrap(), "Bearer as_token");
+    }
+
+    #[test]
+    fn appservice_request_with_user_id() {
+        let user_id = user_id!("@_virtual_:ruma.io");
+        let http_req = Request {}
+            .try_into_appservice_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::Appservice("as_token"),
+                user_id,
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+        assert_eq!(http_req.uri().query(), Some("user_id=%40_virtual_%3Aruma.io"));
+        assert_eq!(http_req.headers().get(AUTHORIZATION).unwrap(), "Bearer as_token");
+    }
+}
+
+mod access_token {
+    use http::header::AUTHORIZATION;
+    use ruma_common::{
+        api::{
+            request, response, MatrixVersion, Metadata, OutgoingRequestAppserviceExt,
+            SendAccessToken,
+        },
+        metadata, user_id,
+    };
+
+    const METADATA: Metadata = metadata! {
+        method: GET,
+        rate_limited: false,
+        authentication: AccessToken,
+        history: {
+            unstable => "/_matrix/foo/access_token",
+        }
+    };
+
+    /// Request type for the `access_token_endpoint` endpoint.
+    #[request]
+    pub struct Request {}
+
+    /// Response type for the `access_token_endpoint` endpoint.
+    #[response]
+    pub struct Response {}
+
+    #[test]
+    fn appservice_request_without_user_id() {
+        let http_req = Request {}
+            .try_into_appservice_http_request::<Vec<u8>>(
+                "https://homeserver.tld",
+                SendAccessToken::IfRequired("access_token"),
+                user_id!("@_virtual_:ruma.io"),
+                &[MatrixVersion::V1_1],
+            )
+            .unwrap();
+
+        assert_eq!(http_req.uri().query(), None);
+        assert_eq!(http_req.headers().get(AUTHORIZATION).unwrap(), "Bearer access_token");
+    }
+}
@@ -7,8 +7,8 @@ use http::{header::CONTENT_TYPE, method::Method};
 use ruma_common::{
     api::{
         error::{FromHttpRequestError, FromHttpResponseError, IntoHttpError, MatrixError},
-        AuthScheme, EndpointError, IncomingRequest, IncomingResponse, MatrixVersion, Metadata,
-        OutgoingRequest, OutgoingResponse, SendAccessToken, VersionHistory,
+        AuthScheme, BodyFormat, EndpointError, IncomingRequest, IncomingResponse, MatrixVersion,
+        Metadata, OutgoingRequest, OutgoingResponse, SendAccessToken, VersionHistory,
     },
     OwnedRoomAliasId, OwnedRoomId,
 };
@@ -34,6 +34,7 @@ const METADATA: Metadata = Metadata {
         Some(MatrixVersion::V1_2),
         Some(MatrixVersion::V1_3),
     ),
+    body_format: BodyFormat::Json,
 };
 
 impl OutgoingRequest for Request {
@@ -40,4 +40,11 @@ impl BeaconEventContent {
             ts: ts.unwrap_or_else(MilliSecondsSinceUnixEpoch::now),
         }
     }
+
+    /// Parses the latitude and longitude from the `geo:` URI of the location of this beacon.
+    ///
+    /// Returns `None` if the URI is not a valid `geo:` URI.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        self.location.coordinates()
+    }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use ruma_common::{
     serde::from_raw_json_value, EventId, MilliSecondsSinceUnixEpoch, OwnedRoomId, RoomId,
     TransactionId, UserId,
@@ -6,7 +8,7 @@ use ruma_macros::{event_enum, EventEnumFromEvent};
 use serde::{de, Deserialize};
 use serde_json::value::RawValue as RawJsonValue;
 
-use super::room::encrypted;
+use super::{room::encrypted, UnsignedRoomRedactionEvent};
 
 event_enum! {
     /// Any global account data event.
@@ -204,6 +206,9 @@ impl AnyTimelineEvent {
 
         /// Returns this event's `transaction_id` from inside `unsigned`, if there is one.
         pub fn transaction_id(&self) -> Option<&TransactionId>;
+
+        /// Returns the redaction event from inside `unsigned`, if this event was redacted.
+        pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent>;
     }
 
     /// Returns this event's `type`.
@@ -241,6 +246,9 @@ impl AnySyncTimelineEvent {
 
         /// Returns this event's `transaction_id` from inside `unsigned`, if there is one.
         pub fn transaction_id(&self) -> Option<&TransactionId>;
+
+        /// Returns the redaction event from inside `unsigned`, if this event was redacted.
+        pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent>;
     }
 
     /// Returns this event's `type`.
@@ -307,6 +315,35 @@ impl<'de> Deserialize<'de> for AnySyncTimelineEvent {
     }
 }
 
+impl TimelineEventType {
+    /// Whether this event type is in the `m.` namespace reserved for the Matrix specification.
+    pub fn is_official(&self) -> bool {
+        self.to_cow_str().starts_with("m.")
+    }
+
+    /// The reverse-DNS namespace of this event type, if it is not an official event type.
+    ///
+    /// This is the part of the event type made of its first two segments, e.g. `com.example` for
+    /// `com.example.thing`. Returns `None` for event types in the `m.` namespace and event types
+    /// with fewer than three segments.
+    pub fn namespace(&self) -> Option<&str> {
+        // None of the variants have a type fragment, so the string is always borrowed.
+        let Cow::Borrowed(event_type) = self.to_cow_str() else {
+            return None;
+        };
+
+        if self.is_official() {
+            return None;
+        }
+
+        let mut dots = event_type.match_indices('.').map(|(idx, _)| idx);
+        dots.next()?;
+        let namespace_end = dots.next()?;
+
+        Some(&event_type[..namespace_end])
+    }
+}
+
 impl AnyMessageLikeEventContent {
     /// Get a copy of the event's `m.relates_to` field, if any.
     ///
@@ -16,7 +16,7 @@ use super::{
     MessageLikeEventType, MessageLikeUnsigned, PossiblyRedactedStateEventContent, RedactContent,
     RedactedMessageLikeEventContent, RedactedStateEventContent, RedactedUnsigned,
     RedactionDeHelper, RoomAccountDataEventContent, StateEventType, StaticStateEventContent,
-    ToDeviceEventContent,
+    ToDeviceEventContent, UnsignedRoomRedactionEvent,
 };
 
 /// A global account data event.
@@ -623,6 +623,14 @@ macro_rules! impl_possibly_redacted_event {
                 }
             }
 
+            /// Returns the redaction event from inside `unsigned`, if this event was redacted.
+            pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent> {
+                match self {
+                    Self::Original(_) => None,
+                    Self::Redacted(ev) => Some(&ev.unsigned.redacted_because),
+                }
+            }
+
             // So the room_id method can be in the same impl block, in rustdoc
             $($extra)*
         }
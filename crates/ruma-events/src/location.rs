@@ -112,6 +112,29 @@ impl LocationContent {
     pub fn new(uri: String) -> Self {
         Self { uri, description: None, zoom_level: None }
     }
+
+    /// Parses the latitude and longitude from the `geo:` URI of this location.
+    ///
+    /// Returns `None` if the URI is not a valid `geo:` URI or if the coordinates are out of range.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        let path = self.uri.strip_prefix("geo:")?;
+        let coords = path.split(';').next()?;
+
+        let mut parts = coords.split(',');
+        let latitude: f64 = parts.next()?.parse().ok()?;
+        let longitude: f64 = parts.next()?.parse().ok()?;
+
+        // An optional altitude is allowed as third coordinate.
+        if let Some(altitude) = parts.next() {
+            altitude.parse::<f64>().ok()?;
+        }
+        if parts.next().is_some() {
+            return None;
+        }
+
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude))
+            .then_some((latitude, longitude))
+    }
 }
 
 /// An error encountered when trying to convert to a `ZoomLevel`.
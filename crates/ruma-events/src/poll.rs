@@ -13,7 +13,10 @@ use indexmap::IndexMap;
 use js_int::{uint, UInt};
 use ruma_common::{MilliSecondsSinceUnixEpoch, UserId};
 
-use self::{start::PollContentBlock, unstable_start::UnstablePollStartContentBlock};
+use self::{
+    start::{PollAnswersError, PollContentBlock},
+    unstable_start::UnstablePollStartContentBlock,
+};
 
 pub mod end;
 pub mod response;
@@ -36,6 +39,41 @@ pub struct PollResponseData<'a> {
     pub selections: &'a [String],
 }
 
+/// The results of a poll.
+///
+/// This is a map of answer ID to the set of user IDs that voted for them, as returned by
+/// [`compile_poll_results()`] and [`compile_unstable_poll_results()`].
+pub type PollTally<'a> = IndexMap<&'a str, BTreeSet<&'a UserId>>;
+
+/// An error encountered when validating a poll or a poll response.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PollError {
+    /// The question of the poll is empty.
+    #[error("the poll question is empty")]
+    EmptyQuestion,
+
+    /// The poll has an invalid number of answers.
+    #[error(transparent)]
+    Answers(#[from] PollAnswersError),
+
+    /// Several answers of the poll have the same ID.
+    #[error("duplicate answer ID `{0}`")]
+    DuplicateAnswerId(String),
+
+    /// The maximum number of selections of the poll is lower than `1`.
+    #[error("the maximum number of selections must be at least 1")]
+    InvalidMaxSelections,
+
+    /// A response selects an answer that is not part of the poll.
+    #[error("unknown answer ID `{0}`")]
+    UnknownAnswerId(String),
+
+    /// A response selects more answers than allowed by the poll.
+    #[error("too many selections")]
+    TooManySelections,
+}
+
 /// Generate the current results with the given poll and responses.
 ///
 /// If the `end_timestamp` is provided, any response with an `origin_server_ts` after that timestamp
@@ -51,7 +89,7 @@ pub fn compile_poll_results<'a>(
     poll: &'a PollContentBlock,
     responses: impl IntoIterator<Item = PollResponseData<'a>>,
     end_timestamp: Option<MilliSecondsSinceUnixEpoch>,
-) -> IndexMap<&'a str, BTreeSet<&'a UserId>> {
+) -> PollTally<'a> {
     let answer_ids = poll.answers.iter().map(|a| a.id.as_str()).collect();
     let users_selections =
         filter_selections(answer_ids, poll.max_selections, responses, end_timestamp);
@@ -74,7 +112,7 @@ pub fn compile_unstable_poll_results<'a>(
     poll: &'a UnstablePollStartContentBlock,
     responses: impl IntoIterator<Item = PollResponseData<'a>>,
     end_timestamp: Option<MilliSecondsSinceUnixEpoch>,
-) -> IndexMap<&'a str, BTreeSet<&'a UserId>> {
+) -> PollTally<'a> {
     let answer_ids = poll.answers.iter().map(|a| a.id.as_str()).collect();
     let users_selections =
         filter_selections(answer_ids, poll.max_selections, responses, end_timestamp);
@@ -135,7 +173,7 @@ fn aggregate_results<'a>(
         &'a UserId,
         (MilliSecondsSinceUnixEpoch, Option<impl Iterator<Item = &'a str>>),
     >,
-) -> IndexMap<&'a str, BTreeSet<&'a UserId>> {
+) -> PollTally<'a> {
     let mut results = IndexMap::from_iter(answers.into_iter().map(|a| (a, BTreeSet::new())));
 
     for (user, (_, selections)) in users_selections {
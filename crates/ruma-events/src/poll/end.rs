@@ -10,6 +10,7 @@ use ruma_common::OwnedEventId;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
+use super::{generate_poll_end_fallback_text, start::OriginalSyncPollStartEvent, PollTally};
 use crate::{message::TextContentBlock, relation::Reference};
 
 /// The payload for a poll end event.
@@ -75,6 +76,38 @@ impl PollEndEventContent {
             relates_to: Reference::new(poll_start_id),
         }
     }
+
+    /// Creates a new `PollEndEventContent` with the given results that responds to the given poll
+    /// start event ID.
+    ///
+    /// The plain text fallback representation lists the IDs of the top answers. To use the text of
+    /// the answers instead, use [`OriginalSyncPollStartEvent::compile_results()`].
+    pub fn from_tally(poll_start_id: OwnedEventId, tally: &PollTally<'_>) -> Self {
+        let mut results = tally.iter().map(|(id, users)| (*id, users.len())).collect::<Vec<_>>();
+        results.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        let answers = results.iter().map(|(id, _)| (*id, *id)).collect::<Vec<_>>();
+        let plain_text = generate_poll_end_fallback_text(&answers, results.iter().copied());
+
+        let mut end = Self::with_plain_text(plain_text, poll_start_id);
+        end.poll_results = Some(PollResultsContentBlock::from_iter(
+            results
+                .into_iter()
+                .map(|(id, count)| (id.to_owned(), count.try_into().unwrap_or(UInt::MAX))),
+        ));
+
+        end
+    }
+}
+
+impl OriginalSyncPollEndEvent {
+    /// Whether this poll end event was sent by the creator of the given poll.
+    ///
+    /// Poll end events sent by other users should usually be ignored, it is up to the caller to
+    /// enforce it.
+    pub fn is_sent_by_poll_creator(&self, poll_start: &OriginalSyncPollStartEvent) -> bool {
+        self.sender == poll_start.sender
+    }
 }
 
 /// A block for the results of a poll.
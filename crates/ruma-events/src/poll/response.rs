@@ -6,7 +6,10 @@ use ruma_common::OwnedEventId;
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
-use super::{start::PollContentBlock, validate_selections, PollResponseData};
+use super::{
+    start::{PollContentBlock, PollStartEventContent},
+    validate_selections, PollError, PollResponseData,
+};
 use crate::relation::Reference;
 
 /// The payload for a poll response event.
@@ -51,6 +54,52 @@ impl PollResponseEventContent {
             relates_to: Reference::new(poll_start_id),
         }
     }
+
+    /// Checks that the selections of this response are valid for the given poll.
+    ///
+    /// Returns an error if a selection is not the ID of an answer of the poll, or if there are
+    /// more selections than the `max_selections` of the poll.
+    pub fn validate_against(&self, poll: &PollStartEventContent) -> Result<(), PollError> {
+        if let Some(unknown) = self
+            .selections
+            .0
+            .iter()
+            .find(|selection| !poll.poll.answers.iter().any(|a| a.id == **selection))
+        {
+            return Err(PollError::UnknownAnswerId(unknown.clone()));
+        }
+
+        if self.selections.0.len() > max_selections(&poll.poll) {
+            return Err(PollError::TooManySelections);
+        }
+
+        Ok(())
+    }
+
+    /// Removes the invalid selections of this response for the given poll.
+    ///
+    /// The selections are truncated to the `max_selections` of the poll, keeping the first ones.
+    /// Then, if any of the remaining selections is not the ID of an answer of the poll, the vote is
+    /// spoiled and all the selections are removed, like when compiling the results of the poll.
+    pub fn sanitize_against(&mut self, poll: &PollStartEventContent) {
+        self.selections.0.truncate(max_selections(&poll.poll));
+
+        if self
+            .selections
+            .0
+            .iter()
+            .any(|selection| !poll.poll.answers.iter().any(|a| a.id == *selection))
+        {
+            self.selections.0.clear();
+        }
+    }
+}
+
+/// The maximum number of selections allowed by the given poll, as a `usize`.
+fn max_selections(poll: &PollContentBlock) -> usize {
+    // Fallback to the maximum value for usize because we can't have more selections than that
+    // in memory.
+    poll.max_selections.try_into().unwrap_or(usize::MAX)
 }
 
 impl OriginalSyncPollResponseEvent {
@@ -1,6 +1,6 @@
 //! Types for the `m.poll.start` event.
 
-use std::ops::Deref;
+use std::{collections::BTreeSet, ops::Deref};
 
 use js_int::{uint, UInt};
 use ruma_common::{serde::StringEnum, MilliSecondsSinceUnixEpoch};
@@ -16,7 +16,7 @@ use poll_answers_serde::PollAnswersDeHelper;
 use super::{
     compile_poll_results,
     end::{PollEndEventContent, PollResultsContentBlock},
-    generate_poll_end_fallback_text, PollResponseData,
+    generate_poll_end_fallback_text, PollError, PollResponseData,
 };
 use crate::{message::TextContentBlock, room::message::Relation};
 
@@ -77,6 +77,15 @@ impl PollStartEventContent {
     pub fn with_plain_text(plain_text: impl Into<String>, poll: PollContentBlock) -> Self {
         Self::new(TextContentBlock::plain(plain_text), poll)
     }
+
+    /// Checks that this poll is valid.
+    ///
+    /// A valid poll has a non-empty question, between [`PollAnswers::MIN_LENGTH`] and
+    /// [`PollAnswers::MAX_LENGTH`] answers with unique IDs, and a `max_selections` of at least
+    /// `1`.
+    pub fn validate(&self) -> Result<(), PollError> {
+        self.poll.validate()
+    }
 }
 
 impl OriginalSyncPollStartEvent {
@@ -161,6 +170,37 @@ impl PollContentBlock {
         }
     }
 
+    /// Checks that this poll is valid.
+    ///
+    /// See [`PollStartEventContent::validate()`] for the checks that are performed.
+    pub fn validate(&self) -> Result<(), PollError> {
+        if self.question.text.is_empty()
+            || self.question.text.iter().all(|representation| representation.body.trim().is_empty())
+        {
+            return Err(PollError::EmptyQuestion);
+        }
+
+        if self.answers.len() < PollAnswers::MIN_LENGTH {
+            return Err(PollAnswersError::NotEnoughValues.into());
+        }
+        if self.answers.len() > PollAnswers::MAX_LENGTH {
+            return Err(PollAnswersError::TooManyValues.into());
+        }
+
+        let mut answer_ids = BTreeSet::new();
+        for answer in self.answers.iter() {
+            if !answer_ids.insert(answer.id.as_str()) {
+                return Err(PollError::DuplicateAnswerId(answer.id.clone()));
+            }
+        }
+
+        if self.max_selections < uint!(1) {
+            return Err(PollError::InvalidMaxSelections);
+        }
+
+        Ok(())
+    }
+
     pub(super) fn default_max_selections() -> UInt {
         uint!(1)
     }
@@ -218,6 +258,24 @@ impl PollAnswers {
 
     /// The largest number of values contained in a `PollAnswers`.
     pub const MAX_LENGTH: usize = 20;
+
+    /// Creates a new `PollAnswers` from the given text representations, generating a unique ID
+    /// for each answer.
+    ///
+    /// The IDs are the 1-based positions of the answers in the list.
+    ///
+    /// Returns an error if there are not between [`PollAnswers::MIN_LENGTH`] and
+    /// [`PollAnswers::MAX_LENGTH`] answers.
+    pub fn with_generated_ids(
+        answers: impl IntoIterator<Item = TextContentBlock>,
+    ) -> Result<Self, PollAnswersError> {
+        answers
+            .into_iter()
+            .enumerate()
+            .map(|(i, text)| PollAnswer::new((i + 1).to_string(), text))
+            .collect::<Vec<_>>()
+            .try_into()
+    }
 }
 
 /// An error encountered when trying to convert to a `PollAnswers`.
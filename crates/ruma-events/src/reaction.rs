@@ -2,8 +2,12 @@
 //!
 //! [`m.reaction`]: https://spec.matrix.org/latest/client-server-api/#mreaction
 
+use std::{borrow::Cow, collections::BTreeMap};
+
+use ruma_common::{EventId, OwnedEventId, OwnedUserId, UserId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
 
 use super::relation::Annotation;
 
@@ -26,6 +30,24 @@ impl ReactionEventContent {
     pub fn new(relates_to: Annotation) -> Self {
         Self { relates_to }
     }
+
+    /// Creates a new `ReactionEventContent` reacting to the event with the given ID with the given
+    /// key.
+    ///
+    /// This always uses an `m.annotation` relation.
+    pub fn annotate(relates_to_event: OwnedEventId, key: String) -> Self {
+        Self::new(Annotation::new(relates_to_event, key))
+    }
+
+    /// The key of this reaction, usually an emoji.
+    pub fn key(&self) -> &str {
+        &self.relates_to.key
+    }
+
+    /// The ID of the event this reaction is for.
+    pub fn relates_to(&self) -> &EventId {
+        &self.relates_to.event_id
+    }
 }
 
 impl From<Annotation> for ReactionEventContent {
@@ -34,13 +56,70 @@ impl From<Annotation> for ReactionEventContent {
     }
 }
 
+/// A group of reactions with the same key, as returned by [`aggregate`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ReactionGroup {
+    /// The users that sent a reaction with this key, in the order of their first reaction.
+    ///
+    /// Each user appears only once, even if they sent several reactions with the same key.
+    pub senders: Vec<OwnedUserId>,
+}
+
+impl ReactionGroup {
+    /// The number of unique users that sent a reaction with this key.
+    pub fn count(&self) -> usize {
+        self.senders.len()
+    }
+}
+
+/// Groups the given reactions by key.
+///
+/// Takes an iterator over the sender and content of reaction events, usually all relating to the
+/// same event, and returns a map from reaction key to the group of users that reacted with it.
+/// The keys are normalized with [`normalize_key`], so different spellings of the same emoji are
+/// grouped together. Duplicate reactions from the same sender with the same key are only counted
+/// once.
+pub fn aggregate<'a>(
+    reactions: impl Iterator<Item = (&'a UserId, &'a ReactionEventContent)>,
+) -> BTreeMap<String, ReactionGroup> {
+    let mut groups = BTreeMap::<String, ReactionGroup>::new();
+
+    for (sender, content) in reactions {
+        let group = groups.entry(normalize_key(content.key()).into_owned()).or_default();
+        if !group.senders.iter().any(|s| s == sender) {
+            group.senders.push(sender.to_owned());
+        }
+    }
+
+    groups
+}
+
+/// Normalizes the given reaction key, to compare keys that represent the same emoji.
+///
+/// This removes the emoji and text variation selectors (`U+FE0F` and `U+FE0E`) and applies the
+/// Unicode Normalization Form C. For example, `👍` and `👍️` have the same normalized key.
+///
+/// Returns the key unchanged if it is already normalized.
+pub fn normalize_key(key: &str) -> Cow<'_, str> {
+    fn is_variation_selector(c: char) -> bool {
+        matches!(c, '\u{FE0E}' | '\u{FE0F}')
+    }
+
+    if !key.contains(is_variation_selector) && is_nfc(key) {
+        return Cow::Borrowed(key);
+    }
+
+    Cow::Owned(key.chars().filter(|&c| !is_variation_selector(c)).nfc().collect())
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches2::assert_matches;
-    use ruma_common::owned_event_id;
+    use ruma_common::{owned_event_id, user_id};
     use serde_json::{from_value as from_json_value, json, to_value as to_json_value};
 
-    use super::ReactionEventContent;
+    use super::{aggregate, normalize_key, ReactionEventContent};
     use crate::relation::Annotation;
 
     #[test]
@@ -79,4 +158,66 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn annotate_accessors() {
+        let content =
+            ReactionEventContent::annotate(owned_event_id!("$my_reaction"), "👍".to_owned());
+
+        assert_eq!(content.key(), "👍");
+        assert_eq!(content.relates_to(), "$my_reaction");
+        assert_eq!(
+            to_json_value(&content).unwrap(),
+            json!({
+                "m.relates_to": {
+                    "rel_type": "m.annotation",
+                    "event_id": "$my_reaction",
+                    "key": "👍"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn aggregate_dedupes_senders() {
+        let alice = user_id!("@alice:localhost");
+        let bob = user_id!("@bob:localhost");
+        let thumbs_up = ReactionEventContent::annotate(owned_event_id!("$target"), "👍".to_owned());
+        let heart = ReactionEventContent::annotate(owned_event_id!("$target"), "❤️".to_owned());
+
+        let reactions =
+            [(alice, &thumbs_up), (bob, &thumbs_up), (alice, &thumbs_up), (bob, &heart)];
+        let groups = aggregate(reactions.into_iter());
+
+        assert_eq!(groups.len(), 2);
+        let thumbs_up_group = &groups["👍"];
+        assert_eq!(thumbs_up_group.count(), 2);
+        assert_eq!(thumbs_up_group.senders, [alice, bob]);
+        let heart_group = &groups[&*normalize_key("❤️")];
+        assert_eq!(heart_group.count(), 1);
+        assert_eq!(heart_group.senders, [bob]);
+    }
+
+    #[test]
+    fn normalize_variant_keys() {
+        assert_eq!(normalize_key("👍"), "👍");
+        assert_eq!(normalize_key("👍\u{FE0F}"), "👍");
+        assert_eq!(normalize_key("❤\u{FE0F}"), normalize_key("❤"));
+        // Decomposed `é` is composed.
+        assert_eq!(normalize_key("e\u{301}"), "\u{E9}");
+    }
+
+    #[test]
+    fn aggregate_normalizes_keys() {
+        let alice = user_id!("@alice:localhost");
+        let bob = user_id!("@bob:localhost");
+        let plain = ReactionEventContent::annotate(owned_event_id!("$target"), "👍".to_owned());
+        let emoji_presentation =
+            ReactionEventContent::annotate(owned_event_id!("$target"), "👍\u{FE0F}".to_owned());
+
+        let groups = aggregate([(alice, &plain), (bob, &emoji_presentation)].into_iter());
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups["👍"].senders, [alice, bob]);
+    }
 }
@@ -12,7 +12,7 @@ use ruma_common::{
 use serde::{Deserialize, Serialize};
 
 use super::AnyMessageLikeEvent;
-use crate::PrivOwnedStr;
+use crate::{MessageLikeEventType, PrivOwnedStr};
 
 mod rel_serde;
 
@@ -203,6 +203,43 @@ impl ReferenceChunk {
     }
 }
 
+/// A bundled annotation, i.e. a group of annotations with the same key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct BundledAnnotation {
+    /// The type of the annotation events, usually `m.reaction`.
+    #[serde(rename = "type")]
+    pub event_type: MessageLikeEventType,
+
+    /// The key of the annotations.
+    pub key: String,
+
+    /// The number of annotations with this key.
+    pub count: UInt,
+}
+
+impl BundledAnnotation {
+    /// Creates a new `BundledAnnotation` with the given event type, key and count.
+    pub fn new(event_type: MessageLikeEventType, key: String, count: UInt) -> Self {
+        Self { event_type, key, count }
+    }
+}
+
+/// A chunk of annotations.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct AnnotationChunk {
+    /// A batch of bundled annotations.
+    pub chunk: Vec<BundledAnnotation>,
+}
+
+impl AnnotationChunk {
+    /// Creates a new `AnnotationChunk` with the given chunk.
+    pub fn new(chunk: Vec<BundledAnnotation>) -> Self {
+        Self { chunk }
+    }
+}
+
 /// [Bundled aggregations] of related child events of a message-like event.
 ///
 /// [Bundled aggregations]: https://spec.matrix.org/latest/client-server-api/#aggregations-of-child-events
@@ -226,12 +263,25 @@ pub struct BundledMessageLikeRelations<E> {
     /// Reference relations.
     #[serde(rename = "m.reference", skip_serializing_if = "Option::is_none")]
     pub reference: Option<Box<ReferenceChunk>>,
+
+    /// Annotation relations, like reactions.
+    ///
+    /// The Matrix specification doesn't require servers to bundle annotations, but some servers
+    /// still do.
+    #[serde(rename = "m.annotation", skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<Box<AnnotationChunk>>,
 }
 
 impl<E> BundledMessageLikeRelations<E> {
     /// Creates a new empty `BundledMessageLikeRelations`.
     pub const fn new() -> Self {
-        Self { replace: None, has_invalid_replacement: false, thread: None, reference: None }
+        Self {
+            replace: None,
+            has_invalid_replacement: false,
+            thread: None,
+            reference: None,
+            annotation: None,
+        }
     }
 
     /// Whether this bundle contains a replacement relation.
@@ -246,15 +296,24 @@ impl<E> BundledMessageLikeRelations<E> {
 
     /// Returns `true` if all fields are empty.
     pub fn is_empty(&self) -> bool {
-        self.replace.is_none() && self.thread.is_none() && self.reference.is_none()
+        self.replace.is_none()
+            && self.thread.is_none()
+            && self.reference.is_none()
+            && self.annotation.is_none()
     }
 
     /// Transform `BundledMessageLikeRelations<E>` to `BundledMessageLikeRelations<T>` using the
     /// given closure to convert the `replace` field if it is `Some(_)`.
     pub(crate) fn map_replace<T>(self, f: impl FnOnce(E) -> T) -> BundledMessageLikeRelations<T> {
-        let Self { replace, has_invalid_replacement, thread, reference } = self;
+        let Self { replace, has_invalid_replacement, thread, reference, annotation } = self;
         let replace = replace.map(|r| Box::new(f(*r)));
-        BundledMessageLikeRelations { replace, has_invalid_replacement, thread, reference }
+        BundledMessageLikeRelations {
+            replace,
+            has_invalid_replacement,
+            thread,
+            reference,
+            annotation,
+        }
     }
 }
 
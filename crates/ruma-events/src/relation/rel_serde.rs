@@ -1,7 +1,7 @@
 use ruma_common::serde::Raw;
 use serde::{de::DeserializeOwned, Deserialize, Deserializer};
 
-use super::{BundledMessageLikeRelations, BundledThread, ReferenceChunk};
+use super::{AnnotationChunk, BundledMessageLikeRelations, BundledThread, ReferenceChunk};
 
 #[derive(Deserialize)]
 struct BundledMessageLikeRelationsJsonRepr<E> {
@@ -11,6 +11,8 @@ struct BundledMessageLikeRelationsJsonRepr<E> {
     thread: Option<Box<BundledThread>>,
     #[serde(rename = "m.reference")]
     reference: Option<Box<ReferenceChunk>>,
+    #[serde(rename = "m.annotation")]
+    annotation: Option<Box<AnnotationChunk>>,
 }
 
 impl<'de, E> Deserialize<'de> for BundledMessageLikeRelations<E>
@@ -21,7 +23,7 @@ where
     where
         D: Deserializer<'de>,
     {
-        let BundledMessageLikeRelationsJsonRepr { replace, thread, reference } =
+        let BundledMessageLikeRelationsJsonRepr { replace, thread, reference, annotation } =
             BundledMessageLikeRelationsJsonRepr::deserialize(deserializer)?;
 
         let (replace, has_invalid_replacement) =
@@ -30,6 +32,12 @@ where
                 Err(_) => (None, true),
             };
 
-        Ok(BundledMessageLikeRelations { replace, has_invalid_replacement, thread, reference })
+        Ok(BundledMessageLikeRelations {
+            replace,
+            has_invalid_replacement,
+            thread,
+            reference,
+            annotation,
+        })
     }
 }
@@ -7,10 +7,14 @@ use std::collections::BTreeMap;
 use js_int::UInt;
 use ruma_common::{
     serde::{base64::UrlSafe, Base64},
-    OwnedMxcUri,
+    MxcUri, OwnedMxcUri,
 };
 use serde::{de, Deserialize, Serialize};
 
+use self::{
+    guest_access::GuestAccess, history_visibility::HistoryVisibility, join_rules::JoinRule,
+};
+
 pub mod aliases;
 pub mod avatar;
 pub mod canonical_alias;
@@ -45,6 +49,18 @@ pub enum MediaSource {
     Encrypted(Box<EncryptedFile>),
 }
 
+impl MediaSource {
+    /// The MXC URI of the media file.
+    ///
+    /// For an encrypted file, this is the URI of the encrypted content.
+    pub fn mxc_uri(&self) -> &MxcUri {
+        match self {
+            Self::Plain(url) => url,
+            Self::Encrypted(file) => &file.url,
+        }
+    }
+}
+
 // Custom implementation of `Deserialize`, because serde doesn't guarantee what variant will be
 // deserialized for "externally tagged"¹ enums where multiple "tag" fields exist.
 //
@@ -111,6 +127,41 @@ impl ImageInfo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Checks whether the declared `mimetype` matches the type detected from the given bytes of
+    /// the image.
+    ///
+    /// The type is detected from the magic bytes at the start of the file. Only PNG, JPEG, GIF,
+    /// WebP and BMP images are detected.
+    ///
+    /// Returns `None` if the `mimetype` is not set or if the type of the bytes can't be detected.
+    pub fn mimetype_matches(&self, bytes: &[u8]) -> Option<bool> {
+        let declared = self.mimetype.as_deref()?;
+        let detected = sniff_image_mimetype(bytes)?;
+
+        let declared = declared.split(';').next().unwrap_or_default().trim();
+        let declared =
+            if declared.eq_ignore_ascii_case("image/jpg") { "image/jpeg" } else { declared };
+
+        Some(declared.eq_ignore_ascii_case(detected))
+    }
+}
+
+/// Detect the MIME type of an image from its magic bytes.
+fn sniff_image_mimetype(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if bytes.starts_with(b"BM") {
+        Some("image/bmp")
+    } else {
+        None
+    }
 }
 
 /// Metadata about a thumbnail.
@@ -273,6 +324,38 @@ impl From<JsonWebKeyInit> for JsonWebKey {
     }
 }
 
+/// Who can access a room, as computed by [`access_summary`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct AccessSummary {
+    /// Whether anyone can read the room's history without joining it.
+    pub world_readable: bool,
+
+    /// Whether anyone can join the room without an invite.
+    pub anyone_can_join: bool,
+
+    /// Whether guest users can join the room.
+    pub guests_can_join: bool,
+}
+
+/// Compute who can access a room from its `m.room.join_rules`, `m.room.guest_access` and
+/// `m.room.history_visibility` state.
+///
+/// Guests can only join a room if it is public and its guest access is [`GuestAccess::CanJoin`].
+pub fn access_summary(
+    join_rule: &JoinRule,
+    guest_access: &GuestAccess,
+    history_visibility: &HistoryVisibility,
+) -> AccessSummary {
+    let anyone_can_join = *join_rule == JoinRule::Public;
+
+    AccessSummary {
+        world_readable: *history_visibility == HistoryVisibility::WorldReadable,
+        anyone_can_join,
+        guests_can_join: anyone_can_join && *guest_access == GuestAccess::CanJoin,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
@@ -282,7 +365,10 @@ mod tests {
     use serde::Deserialize;
     use serde_json::{from_value as from_json_value, json};
 
-    use super::{EncryptedFile, JsonWebKey, MediaSource};
+    use super::{
+        access_summary, guest_access::GuestAccess, history_visibility::HistoryVisibility,
+        join_rules::JoinRule, EncryptedFile, ImageInfo, JsonWebKey, MediaSource,
+    };
 
     #[derive(Deserialize)]
     struct MsgWithAttachment {
@@ -333,4 +419,44 @@ mod tests {
 
         assert_matches!(msg.source, MediaSource::Encrypted(_));
     }
+
+    #[test]
+    fn image_info_mimetype_matches() {
+        const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+        const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F'];
+
+        let mut info = ImageInfo::new();
+        assert_eq!(info.mimetype_matches(PNG), None);
+
+        info.mimetype = Some("image/png".to_owned());
+        assert_eq!(info.mimetype_matches(PNG), Some(true));
+        assert_eq!(info.mimetype_matches(JPEG), Some(false));
+        assert_eq!(info.mimetype_matches(b"not an image"), None);
+
+        info.mimetype = Some("image/jpg".to_owned());
+        assert_eq!(info.mimetype_matches(JPEG), Some(true));
+    }
+
+    #[test]
+    fn access_summary_public_world_readable() {
+        let summary = access_summary(
+            &JoinRule::Public,
+            &GuestAccess::CanJoin,
+            &HistoryVisibility::WorldReadable,
+        );
+
+        assert!(summary.world_readable);
+        assert!(summary.anyone_can_join);
+        assert!(summary.guests_can_join);
+    }
+
+    #[test]
+    fn access_summary_private_invite_only() {
+        let summary =
+            access_summary(&JoinRule::Invite, &GuestAccess::CanJoin, &HistoryVisibility::Shared);
+
+        assert!(!summary.world_readable);
+        assert!(!summary.anyone_can_join);
+        assert!(!summary.guests_can_join);
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! [`m.room.encryption`]: https://spec.matrix.org/latest/client-server-api/#mroomencryption
 
+use std::time::Duration;
+
 use js_int::{uint, UInt};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
@@ -52,4 +54,30 @@ impl RoomEncryptionEventContent {
             rotation_period_msgs: Some(uint!(100)),
         }
     }
+
+    /// How long the session should be used before changing it, if set.
+    pub fn rotation_period(&self) -> Option<Duration> {
+        self.rotation_period_ms.map(|ms| Duration::from_millis(ms.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use js_int::uint;
+
+    use super::RoomEncryptionEventContent;
+    use crate::EventEncryptionAlgorithm;
+
+    #[test]
+    fn recommended_defaults_rotation() {
+        let content = RoomEncryptionEventContent::with_recommended_defaults();
+        assert_eq!(content.algorithm, EventEncryptionAlgorithm::MegolmV1AesSha2);
+        assert_eq!(content.rotation_period(), Some(Duration::from_secs(7 * 24 * 60 * 60)));
+        assert_eq!(content.rotation_period_msgs, Some(uint!(100)));
+
+        let content = RoomEncryptionEventContent::new(EventEncryptionAlgorithm::MegolmV1AesSha2);
+        assert_eq!(content.rotation_period(), None);
+    }
 }
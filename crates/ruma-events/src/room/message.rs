@@ -2,7 +2,7 @@
 //!
 //! [`m.room.message`]: https://spec.matrix.org/latest/client-server-api/#mroommessage
 
-use std::borrow::Cow;
+use std::{borrow::Cow, io};
 
 use ruma_common::{
     serde::{JsonObject, Raw, StringEnum},
@@ -15,9 +15,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use tracing::warn;
 
-use self::reply::OriginalEventData;
-#[cfg(feature = "html")]
-use self::sanitize::remove_plain_reply_fallback;
+use self::{reply::OriginalEventData, sanitize::remove_plain_reply_fallback};
 use crate::{
     relation::{InReplyTo, Replacement, Thread},
     AnySyncTimelineEvent, Mentions, PrivOwnedStr,
@@ -30,6 +28,7 @@ mod file;
 mod image;
 mod key_verification_request;
 mod location;
+mod media_caption;
 mod notice;
 mod relation;
 pub(crate) mod relation_serde;
@@ -51,6 +50,7 @@ pub use self::{
     image::ImageMessageEventContent,
     key_verification_request::KeyVerificationRequestEventContent,
     location::{LocationInfo, LocationMessageEventContent},
+    media_caption::MediaCaption,
     notice::NoticeMessageEventContent,
     relation::{Relation, RelationWithoutReplacement},
     relation_serde::deserialize_relation,
@@ -146,6 +146,11 @@ impl RoomMessageEventContent {
         Self::new(MessageType::emote_markdown(body))
     }
 
+    /// A constructor to create a server notice.
+    pub fn server_notice(body: impl Into<String>, server_notice_type: ServerNoticeType) -> Self {
+        Self::new(MessageType::server_notice(body, server_notice_type))
+    }
+
     /// Turns `self` into a reply to the given message.
     ///
     /// Takes the `body` / `formatted_body` (if any) in `self` for the main text and prepends a
@@ -329,6 +334,148 @@ impl RoomMessageEventContent {
         self.msgtype.body()
     }
 
+    /// Parses this message as a slash command, like `/join #room:example.org`.
+    ///
+    /// Returns the command name without the leading `/` and its arguments, with the leading
+    /// whitespace removed. Returns `None` if this is not an `m.text` message or if its body
+    /// doesn't start with a single `/`. A body starting with `//` is the escaped form of a
+    /// message starting with `/`, so it is not a command either.
+    pub fn as_command(&self) -> Option<(&str, &str)> {
+        let MessageType::Text(text) = &self.msgtype else {
+            return None;
+        };
+
+        let command_line = text.body.strip_prefix('/')?;
+        if command_line.starts_with('/') {
+            return None;
+        }
+
+        let (command, args) =
+            command_line.split_once(char::is_whitespace).unwrap_or((command_line, ""));
+        if command.is_empty() {
+            return None;
+        }
+
+        Some((command, args.trim_start()))
+    }
+
+    /// Returns a reference to the relation of this message, if any.
+    ///
+    /// This is a single entry point to know whether this message is a reply, an edit or part of a
+    /// thread, and to which event it relates.
+    pub fn relation(&self) -> Option<&Relation<RoomMessageEventContentWithoutRelation>> {
+        self.relates_to.as_ref()
+    }
+
+    /// Get a short plain text preview of this message, for example for notifications.
+    ///
+    /// The preview depends on the message type, and the rich reply fallback is removed from
+    /// replies. Line breaks are replaced by spaces.
+    ///
+    /// If the preview is longer than `max_chars` characters, it is truncated on a character
+    /// boundary and ends with an ellipsis, so that it is at most `max_chars` characters long.
+    pub fn preview(&self, max_chars: usize) -> String {
+        let body = match &self.msgtype {
+            MessageType::Location(m) => m.plain_text_representation(),
+            msgtype => msgtype.body(),
+        };
+        let body = if matches!(self.relates_to, Some(Relation::Reply { .. })) {
+            remove_plain_reply_fallback(body)
+        } else {
+            body
+        };
+
+        let prefix = if matches!(self.msgtype, MessageType::Emote(_)) { "* " } else { "" };
+        let mut chars = prefix.chars().chain(body.trim().chars()).map(|c| match c {
+            '\r' | '\n' => ' ',
+            c => c,
+        });
+
+        let mut preview: String = chars.by_ref().take(max_chars).collect();
+        if chars.next().is_some() {
+            // Replace the last character with an ellipsis.
+            preview.pop();
+            if max_chars > 0 {
+                preview.push('…');
+            }
+        }
+
+        preview
+    }
+
+    /// Estimate the size of this content once serialized, in bytes.
+    ///
+    /// This is the length of the canonical JSON representation of this content, it doesn't include
+    /// the other fields of the event.
+    pub fn estimated_wire_size(&self) -> usize {
+        struct ByteCounter(usize);
+
+        impl io::Write for ByteCounter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0 += buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut counter = ByteCounter(0);
+        serde_json::to_writer(&mut counter, self)
+            .expect("serializing a RoomMessageEventContent should not fail");
+        counter.0
+    }
+
+    /// Split the body of this message into several messages whose bodies are at most `max_bytes`
+    /// long.
+    ///
+    /// The body is split on character boundaries. If a single character is longer than
+    /// `max_bytes`, it is put in its own message.
+    ///
+    /// This only splits text, notice and emote messages, the formatted body is dropped from the
+    /// split messages since HTML cannot be split safely. The relation and mentions are only kept
+    /// on the first message. Other message types, or messages whose body is short enough, are
+    /// returned unchanged.
+    pub fn split_body(&self, max_bytes: usize) -> Vec<Self> {
+        let body = self.body();
+        if body.len() <= max_bytes {
+            return vec![self.clone()];
+        }
+
+        let make_msgtype: fn(String) -> MessageType = match &self.msgtype {
+            MessageType::Text(_) => MessageType::text_plain,
+            MessageType::Notice(_) => MessageType::notice_plain,
+            MessageType::Emote(_) => {
+                |body| MessageType::Emote(EmoteMessageEventContent::plain(body))
+            }
+            _ => return vec![self.clone()],
+        };
+
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        while start < body.len() {
+            let mut end = start;
+            for (i, c) in body[start..].char_indices() {
+                let char_end = start + i + c.len_utf8();
+                if char_end - start > max_bytes && end > start {
+                    break;
+                }
+                end = char_end;
+            }
+
+            chunks.push(Self::new(make_msgtype(body[start..end].to_owned())));
+            start = end;
+        }
+
+        if let Some(first) = chunks.first_mut() {
+            first.relates_to.clone_from(&self.relates_to);
+            first.mentions.clone_from(&self.mentions);
+        }
+
+        chunks
+    }
+
     /// Apply the given new content from a [`Replacement`] to this message.
     pub fn apply_replacement(&mut self, new_content: RoomMessageEventContentWithoutRelation) {
         let RoomMessageEventContentWithoutRelation { msgtype, mentions } = new_content;
@@ -555,6 +702,11 @@ impl MessageType {
         Self::Emote(EmoteMessageEventContent::markdown(body))
     }
 
+    /// A constructor to create a server notice.
+    pub fn server_notice(body: impl Into<String>, server_notice_type: ServerNoticeType) -> Self {
+        Self::ServerNotice(ServerNoticeMessageEventContent::new(body.into(), server_notice_type))
+    }
+
     /// Returns a reference to the `msgtype` string.
     pub fn msgtype(&self) -> &str {
         match self {
@@ -572,6 +724,16 @@ impl MessageType {
         }
     }
 
+    /// Returns the `msgtype` string of a custom message type.
+    ///
+    /// Returns `None` if this is one of the message types supported by Ruma.
+    pub fn custom_msgtype(&self) -> Option<&str> {
+        match self {
+            Self::_Custom(c) => Some(&c.msgtype),
+            _ => None,
+        }
+    }
+
     /// Return a reference to the message body.
     pub fn body(&self) -> &str {
         match self {
@@ -596,6 +758,10 @@ impl MessageType {
     ///
     /// Prefer to use the public variants of `MessageType` where possible; this method is meant to
     /// be used for custom message types only.
+    ///
+    /// Custom message types are serialized with their fields sorted by key, including `msgtype`
+    /// and `body`. The original order of the fields is not kept, so only JSON with sorted keys,
+    /// like canonical JSON, is serialized back byte for byte.
     pub fn data(&self) -> Cow<'_, JsonObject> {
         fn serialize<T: Serialize>(obj: &T) -> JsonObject {
             match serde_json::to_value(obj).expect("message type serialization to succeed") {
@@ -622,6 +788,29 @@ impl MessageType {
         }
     }
 
+    /// Deserializes a custom message type into the given type.
+    ///
+    /// The JSON object that `T` is deserialized from contains the `msgtype` and `body` fields
+    /// along with the associated [`data`](Self::data), just like the original event content.
+    ///
+    /// Returns `None` if this is not a custom message type, i.e. if it is one of the message types
+    /// supported by Ruma.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deserialization to `T` fails.
+    pub fn try_into_custom<T: DeserializeOwned>(&self) -> Option<serde_json::Result<T>> {
+        let Self::_Custom(c) = self else {
+            return None;
+        };
+
+        let mut obj = c.data.clone();
+        obj.insert("msgtype".into(), c.msgtype.clone().into());
+        obj.insert("body".into(), c.body.clone().into());
+
+        Some(serde_json::from_value(JsonValue::Object(obj)))
+    }
+
     /// Sanitize this message.
     ///
     /// If this message contains HTML, this removes the [tags and attributes] that are not listed in
@@ -811,6 +1000,11 @@ impl FormattedBody {
         Self { format: MessageFormat::Html, body: body.into() }
     }
 
+    /// Whether the format of this `FormattedBody` is `MessageFormat::Html`.
+    pub fn is_html(&self) -> bool {
+        self.format == MessageFormat::Html
+    }
+
     /// Creates a new HTML-formatted message body by parsing the Markdown in `body`.
     ///
     /// Returns `None` if no Markdown formatting was found.
@@ -843,7 +1037,7 @@ impl FormattedBody {
 
 /// The payload for a custom message event.
 #[doc(hidden)]
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct CustomEventContent {
     /// A custom msgtype.
     msgtype: String,
@@ -856,6 +1050,39 @@ pub struct CustomEventContent {
     data: JsonObject,
 }
 
+impl Serialize for CustomEventContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap as _;
+
+        // Interleave `body` and `msgtype` with the remaining fields, sorted by key, so that
+        // canonical JSON is serialized back without any change.
+        let mut data = self.data.iter().collect::<Vec<_>>();
+        data.sort_unstable_by_key(|(key, _)| key.as_str());
+
+        let mut fields = [("body", &self.body), ("msgtype", &self.msgtype)].into_iter().peekable();
+        let mut map = serializer.serialize_map(Some(self.data.len() + 2))?;
+
+        for (key, value) in data {
+            while let Some((field, field_value)) =
+                fields.next_if(|(field, _)| *field < key.as_str())
+            {
+                map.serialize_entry(field, field_value)?;
+            }
+
+            map.serialize_entry(key, value)?;
+        }
+
+        for (field, field_value) in fields {
+            map.serialize_entry(field, field_value)?;
+        }
+
+        map.end()
+    }
+}
+
 #[cfg(feature = "markdown")]
 pub(crate) fn parse_markdown(text: &str) -> Option<String> {
     use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
@@ -1,6 +1,6 @@
 //! `Deserialize` implementation for RoomMessageEventContent and MessageType.
 
-use ruma_common::serde::from_raw_json_value;
+use ruma_common::serde::{deserialize_tagged_enum, from_raw_json_value};
 use serde::{de, Deserialize};
 use serde_json::value::RawValue as RawJsonValue;
 
@@ -45,33 +45,27 @@ struct MentionsDeHelper {
     mentions: Option<Mentions>,
 }
 
-/// Helper struct to determine the msgtype from a `serde_json::value::RawValue`
-#[derive(Debug, Deserialize)]
-struct MessageTypeDeHelper {
-    /// The message type field
-    msgtype: String,
-}
-
 impl<'de> Deserialize<'de> for MessageType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        let json = Box::<RawJsonValue>::deserialize(deserializer)?;
-        let MessageTypeDeHelper { msgtype } = from_raw_json_value(&json)?;
-
-        Ok(match msgtype.as_ref() {
-            "m.audio" => Self::Audio(from_raw_json_value(&json)?),
-            "m.emote" => Self::Emote(from_raw_json_value(&json)?),
-            "m.file" => Self::File(from_raw_json_value(&json)?),
-            "m.image" => Self::Image(from_raw_json_value(&json)?),
-            "m.location" => Self::Location(from_raw_json_value(&json)?),
-            "m.notice" => Self::Notice(from_raw_json_value(&json)?),
-            "m.server_notice" => Self::ServerNotice(from_raw_json_value(&json)?),
-            "m.text" => Self::Text(from_raw_json_value(&json)?),
-            "m.video" => Self::Video(from_raw_json_value(&json)?),
-            "m.key.verification.request" => Self::VerificationRequest(from_raw_json_value(&json)?),
-            _ => Self::_Custom(from_raw_json_value(&json)?),
+        deserialize_tagged_enum(deserializer, "msgtype", |msgtype, json| {
+            Ok(match msgtype {
+                "m.audio" => Self::Audio(from_raw_json_value(json)?),
+                "m.emote" => Self::Emote(from_raw_json_value(json)?),
+                "m.file" => Self::File(from_raw_json_value(json)?),
+                "m.image" => Self::Image(from_raw_json_value(json)?),
+                "m.location" => Self::Location(from_raw_json_value(json)?),
+                "m.notice" => Self::Notice(from_raw_json_value(json)?),
+                "m.server_notice" => Self::ServerNotice(from_raw_json_value(json)?),
+                "m.text" => Self::Text(from_raw_json_value(json)?),
+                "m.video" => Self::Video(from_raw_json_value(json)?),
+                "m.key.verification.request" => {
+                    Self::VerificationRequest(from_raw_json_value(json)?)
+                }
+                _ => Self::_Custom(from_raw_json_value(json)?),
+            })
         })
     }
 }
@@ -52,6 +52,23 @@ impl FileMessageEventContent {
         Self::new(body, MediaSource::Encrypted(Box::new(file)))
     }
 
+    /// Creates a new encrypted `FileMessageEventContent` with the given body, encrypted file and
+    /// encrypted thumbnail.
+    ///
+    /// The `info` field is set with the `thumbnail_source` and `thumbnail_info` of the thumbnail.
+    pub fn encrypted_with_thumbnail(
+        body: String,
+        file: EncryptedFile,
+        thumbnail_file: EncryptedFile,
+        thumbnail_info: ThumbnailInfo,
+    ) -> Self {
+        let mut info = FileInfo::new();
+        info.thumbnail_source = Some(MediaSource::Encrypted(Box::new(thumbnail_file)));
+        info.thumbnail_info = Some(Box::new(thumbnail_info));
+
+        Self::encrypted(body, file).info(Box::new(info))
+    }
+
     /// Creates a new `FileMessageEventContent` from `self` with the `info` field set to the given
     /// value.
     ///
@@ -2,7 +2,7 @@ use ruma_common::OwnedMxcUri;
 use serde::{Deserialize, Serialize};
 
 use super::FormattedBody;
-use crate::room::{EncryptedFile, ImageInfo, MediaSource};
+use crate::room::{EncryptedFile, ImageInfo, MediaSource, ThumbnailInfo};
 
 /// The payload for an image message.
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -51,6 +51,23 @@ impl ImageMessageEventContent {
         Self::new(body, MediaSource::Encrypted(Box::new(file)))
     }
 
+    /// Creates a new encrypted `ImageMessageEventContent` with the given body, encrypted file and
+    /// encrypted thumbnail.
+    ///
+    /// The `info` field is set with the `thumbnail_source` and `thumbnail_info` of the thumbnail.
+    pub fn encrypted_with_thumbnail(
+        body: String,
+        file: EncryptedFile,
+        thumbnail_file: EncryptedFile,
+        thumbnail_info: ThumbnailInfo,
+    ) -> Self {
+        let mut info = ImageInfo::new();
+        info.thumbnail_source = Some(MediaSource::Encrypted(Box::new(thumbnail_file)));
+        info.thumbnail_info = Some(Box::new(thumbnail_info));
+
+        Self::encrypted(body, file).info(Box::new(info))
+    }
+
     /// Creates a new `ImageMessageEventContent` from `self` with the `info` field set to the given
     /// value.
     ///
@@ -0,0 +1,69 @@
+//! Common accessors for the caption of media message types.
+
+use super::{
+    AudioMessageEventContent, FileMessageEventContent, FormattedBody, ImageMessageEventContent,
+    VideoMessageEventContent,
+};
+
+/// Access to the filename and caption of a media message.
+///
+/// In media messages, the `body` is the filename of the uploaded file if the `filename` field is
+/// not set or has the same value. Otherwise, the `body` is a caption written by the user.
+pub trait MediaCaption {
+    /// The `body` of the message.
+    fn body(&self) -> &str;
+
+    /// The `formatted` form of the `body` of the message.
+    fn formatted(&self) -> Option<&FormattedBody>;
+
+    /// The `filename` field of the message.
+    fn original_filename(&self) -> Option<&str>;
+
+    /// Computes the filename of the media.
+    ///
+    /// This is the `filename` field if it is set, otherwise the `body`.
+    fn filename(&self) -> &str {
+        self.original_filename().unwrap_or_else(|| self.body())
+    }
+
+    /// Returns the caption of the media, if any.
+    ///
+    /// This is the `body` if the `filename` field is set and has a different value.
+    fn caption(&self) -> Option<&str> {
+        self.original_filename().filter(|filename| *filename != self.body()).map(|_| self.body())
+    }
+
+    /// Returns the formatted caption of the media, if any.
+    ///
+    /// This is only set if the message has a [`caption`](Self::caption).
+    fn formatted_caption(&self) -> Option<&FormattedBody> {
+        self.caption().and(self.formatted())
+    }
+}
+
+macro_rules! impl_media_caption {
+    ($($ty:ident),*) => {
+        $(
+            impl MediaCaption for $ty {
+                fn body(&self) -> &str {
+                    &self.body
+                }
+
+                fn formatted(&self) -> Option<&FormattedBody> {
+                    self.formatted.as_ref()
+                }
+
+                fn original_filename(&self) -> Option<&str> {
+                    self.filename.as_deref()
+                }
+            }
+        )*
+    };
+}
+
+impl_media_caption!(
+    AudioMessageEventContent,
+    FileMessageEventContent,
+    ImageMessageEventContent,
+    VideoMessageEventContent
+);
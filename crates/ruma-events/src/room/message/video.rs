@@ -1,8 +1,9 @@
-use std::time::Duration;
+use std::{collections::BTreeMap, time::Duration};
 
 use js_int::UInt;
 use ruma_common::OwnedMxcUri;
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
 use super::FormattedBody;
 use crate::room::{EncryptedFile, MediaSource, ThumbnailInfo};
@@ -54,6 +55,23 @@ impl VideoMessageEventContent {
         Self::new(body, MediaSource::Encrypted(Box::new(file)))
     }
 
+    /// Creates a new encrypted `VideoMessageEventContent` with the given body, encrypted file and
+    /// encrypted thumbnail.
+    ///
+    /// The `info` field is set with the `thumbnail_source` and `thumbnail_info` of the thumbnail.
+    pub fn encrypted_with_thumbnail(
+        body: String,
+        file: EncryptedFile,
+        thumbnail_file: EncryptedFile,
+        thumbnail_info: ThumbnailInfo,
+    ) -> Self {
+        let mut info = VideoInfo::new();
+        info.thumbnail_source = Some(MediaSource::Encrypted(Box::new(thumbnail_file)));
+        info.thumbnail_info = Some(Box::new(thumbnail_info));
+
+        Self::encrypted(body, file).info(Box::new(info))
+    }
+
     /// Creates a new `VideoMessageEventContent` from `self` with the `info` field set to the given
     /// value.
     ///
@@ -111,6 +129,13 @@ pub struct VideoInfo {
     #[cfg(feature = "unstable-msc2448")]
     #[serde(rename = "xyz.amorgan.blurhash", skip_serializing_if = "Option::is_none")]
     pub blurhash: Option<String>,
+
+    /// Other fields of the metadata that are not known to Ruma.
+    ///
+    /// This includes the fields behind a cargo feature that is not enabled, so that they are kept
+    /// when the `VideoInfo` is serialized again.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, JsonValue>,
 }
 
 impl VideoInfo {
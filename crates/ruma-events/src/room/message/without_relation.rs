@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     AddMentions, ForwardThread, MessageType, OriginalRoomMessageEvent, Relation,
-    ReplacementMetadata, ReplyWithinThread, RoomMessageEventContent,
+    ReplacementMetadata, ReplyWithinThread, RoomMessageEventContent, ServerNoticeType,
 };
 use crate::{
     relation::{InReplyTo, Replacement, Thread},
@@ -83,6 +83,11 @@ impl RoomMessageEventContentWithoutRelation {
         Self::new(MessageType::emote_markdown(body))
     }
 
+    /// A constructor to create a server notice.
+    pub fn server_notice(body: impl Into<String>, server_notice_type: ServerNoticeType) -> Self {
+        Self::new(MessageType::server_notice(body, server_notice_type))
+    }
+
     /// Transform `self` into a `RoomMessageEventContent` with the given relation.
     pub fn with_relation(
         self,
@@ -2,7 +2,7 @@
 //!
 //! [`m.room.pinned_events`]: https://spec.matrix.org/latest/client-server-api/#mroompinned_events
 
-use ruma_common::OwnedEventId;
+use ruma_common::{EventId, OwnedEventId};
 use ruma_macros::EventContent;
 use serde::{Deserialize, Serialize};
 
@@ -24,11 +24,32 @@ impl RoomPinnedEventsEventContent {
     pub fn new(pinned: Vec<OwnedEventId>) -> Self {
         Self { pinned }
     }
+
+    /// Pins the event with the given ID.
+    ///
+    /// The event is appended at the end of the list. Does nothing if the event is already pinned.
+    pub fn pin(&mut self, event_id: OwnedEventId) {
+        if !self.is_pinned(&event_id) {
+            self.pinned.push(event_id);
+        }
+    }
+
+    /// Unpins the event with the given ID.
+    ///
+    /// The order of the remaining pinned events is preserved.
+    pub fn unpin(&mut self, event_id: &EventId) {
+        self.pinned.retain(|pinned| pinned != event_id);
+    }
+
+    /// Whether the event with the given ID is pinned.
+    pub fn is_pinned(&self, event_id: &EventId) -> bool {
+        self.pinned.iter().any(|pinned| pinned == event_id)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use ruma_common::owned_event_id;
+    use ruma_common::{event_id, owned_event_id};
 
     use super::RoomPinnedEventsEventContent;
 
@@ -46,4 +67,31 @@ mod tests {
 
         assert_eq!(parsed_content.pinned, content.pinned);
     }
+
+    #[test]
+    fn pin_twice() {
+        let mut content =
+            RoomPinnedEventsEventContent::new(vec![owned_event_id!("$a:example.com")]);
+
+        content.pin(owned_event_id!("$b:example.com"));
+        content.pin(owned_event_id!("$a:example.com"));
+
+        assert_eq!(content.pinned, [event_id!("$a:example.com"), event_id!("$b:example.com")]);
+        assert!(content.is_pinned(event_id!("$b:example.com")));
+    }
+
+    #[test]
+    fn unpin() {
+        let mut content = RoomPinnedEventsEventContent::new(vec![
+            owned_event_id!("$a:example.com"),
+            owned_event_id!("$b:example.com"),
+            owned_event_id!("$c:example.com"),
+        ]);
+
+        content.unpin(event_id!("$b:example.com"));
+        content.unpin(event_id!("$d:example.com"));
+
+        assert_eq!(content.pinned, [event_id!("$a:example.com"), event_id!("$c:example.com")]);
+        assert!(!content.is_pinned(event_id!("$b:example.com")));
+    }
 }
@@ -126,6 +126,35 @@ impl RoomPowerLevelsEventContent {
             notifications: NotificationPowerLevels::default(),
         }
     }
+
+    /// Sets the power level of the given user.
+    ///
+    /// Returns the previous power level of the user, if it was set explicitly.
+    pub fn set_user_level(&mut self, user_id: OwnedUserId, level: Int) -> Option<Int> {
+        self.users.insert(user_id, level)
+    }
+
+    /// Removes the power level of the given user, so that they get `users_default`.
+    ///
+    /// Returns the previous power level of the user, if it was set explicitly.
+    pub fn remove_user(&mut self, user_id: &UserId) -> Option<Int> {
+        self.users.remove(user_id)
+    }
+
+    /// Sets the power level required to send events of the given type.
+    ///
+    /// Returns the previous power level for the event type, if it was set explicitly.
+    pub fn set_event_level(&mut self, event_type: TimelineEventType, level: Int) -> Option<Int> {
+        self.events.insert(event_type, level)
+    }
+
+    /// Removes the power level required to send events of the given type, so that
+    /// `events_default` or `state_default` is used instead.
+    ///
+    /// Returns the previous power level for the event type, if it was set explicitly.
+    pub fn remove_event(&mut self, event_type: &TimelineEventType) -> Option<Int> {
+        self.events.remove(event_type)
+    }
 }
 
 impl Default for RoomPowerLevelsEventContent {
@@ -704,6 +733,34 @@ mod tests {
     use serde_json::{json, to_value as to_json_value};
 
     use super::{default_power_level, NotificationPowerLevels, RoomPowerLevelsEventContent};
+    use crate::TimelineEventType;
+
+    #[test]
+    fn set_and_remove_levels() {
+        let alice = user_id!("@alice:example.org");
+        let bob = user_id!("@bob:example.org");
+        let mut power_levels = RoomPowerLevelsEventContent::new();
+        power_levels.set_user_level(alice.to_owned(), int!(100));
+
+        assert_eq!(power_levels.set_user_level(bob.to_owned(), int!(50)), None);
+        assert_eq!(power_levels.set_user_level(bob.to_owned(), int!(75)), Some(int!(50)));
+        assert_eq!(
+            power_levels.users,
+            btreemap! {
+                alice.to_owned() => int!(100),
+                bob.to_owned() => int!(75),
+            }
+        );
+
+        assert_eq!(power_levels.remove_user(bob), Some(int!(75)));
+        assert_eq!(power_levels.remove_user(bob), None);
+        assert_eq!(power_levels.users, btreemap! { alice.to_owned() => int!(100) });
+
+        assert_eq!(power_levels.set_event_level(TimelineEventType::RoomName, int!(50)), None);
+        assert_eq!(power_levels.events, btreemap! { TimelineEventType::RoomName => int!(50) });
+        assert_eq!(power_levels.remove_event(&TimelineEventType::RoomName), Some(int!(50)));
+        assert!(power_levels.events.is_empty());
+    }
 
     #[test]
     fn serialization_with_optional_fields_as_none() {
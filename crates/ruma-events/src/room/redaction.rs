@@ -17,6 +17,7 @@ use tracing::error;
 use crate::{
     BundledMessageLikeRelations, EventContent, MessageLikeEventType, RedactContent,
     RedactedMessageLikeEventContent, RedactedUnsigned, StaticEventContent,
+    UnsignedRoomRedactionEvent,
 };
 
 mod event_serde;
@@ -312,6 +313,14 @@ impl RoomRedactionEvent {
         }
     }
 
+    /// Returns the redaction event from inside `unsigned`, if this event was redacted.
+    pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent> {
+        match self {
+            Self::Original(_) => None,
+            Self::Redacted(ev) => Some(&ev.unsigned.redacted_because),
+        }
+    }
+
     /// Get the inner `RoomRedactionEvent` if this is an unredacted event.
     pub fn as_original(&self) -> Option<&OriginalRoomRedactionEvent> {
         as_variant!(self, Self::Original)
@@ -364,6 +373,14 @@ impl SyncRoomRedactionEvent {
         }
     }
 
+    /// Returns the redaction event from inside `unsigned`, if this event was redacted.
+    pub fn redacted_because(&self) -> Option<&UnsignedRoomRedactionEvent> {
+        match self {
+            Self::Original(_) => None,
+            Self::Redacted(ev) => Some(&ev.unsigned.redacted_because),
+        }
+    }
+
     /// Get the inner `SyncRoomRedactionEvent` if this is an unredacted event.
     pub fn as_original(&self) -> Option<&OriginalSyncRoomRedactionEvent> {
         as_variant!(self, Self::Original)
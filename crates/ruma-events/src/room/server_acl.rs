@@ -135,6 +135,17 @@ mod tests {
         assert!(acl_event.is_allowed(server_name!("conduit.rs")));
     }
 
+    #[test]
+    fn acl_deny_takes_precedence() {
+        let acl_event = RoomServerAclEventContent {
+            allow_ip_literals: false,
+            allow: vec!["*.matrix.org".to_owned()],
+            deny: vec!["evil.matrix.org".to_owned()],
+        };
+        assert!(!acl_event.is_allowed(server_name!("evil.matrix.org")));
+        assert!(acl_event.is_allowed(server_name!("good.matrix.org")));
+    }
+
     #[test]
     fn acl_explicit_allow() {
         let acl_event = RoomServerAclEventContent {
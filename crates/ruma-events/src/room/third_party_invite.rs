@@ -49,6 +49,29 @@ impl RoomThirdPartyInviteEventContent {
     pub fn new(display_name: String, key_validity_url: String, public_key: Base64) -> Self {
         Self { display_name, key_validity_url, public_key, public_keys: None }
     }
+
+    /// Returns all the public keys with which the token may be signed.
+    ///
+    /// This is the key in the `public_key` field, followed by the keys in the `public_keys` field.
+    pub fn all_public_keys(&self) -> impl Iterator<Item = &Base64> {
+        std::iter::once(&self.public_key)
+            .chain(self.public_keys.iter().flatten().map(|key| &key.public_key))
+    }
+
+    /// Returns all the URLs which can be fetched to validate whether a key has been revoked.
+    ///
+    /// This is the URL in the `key_validity_url` field if it is not empty, followed by the URLs
+    /// of the keys in the `public_keys` field.
+    pub fn key_validity_urls(&self) -> impl Iterator<Item = &str> {
+        Some(self.key_validity_url.as_str()).filter(|url| !url.is_empty()).into_iter().chain(
+            self.public_keys.iter().flatten().filter_map(|key| key.key_validity_url.as_deref()),
+        )
+    }
+
+    /// Whether the given key is one of the public keys with which the token may be signed.
+    pub fn has_public_key(&self, public_key: &Base64) -> bool {
+        self.all_public_keys().any(|key| key == public_key)
+    }
 }
 
 /// A public key for signing a third party invite token.
@@ -72,3 +95,44 @@ impl PublicKey {
         Self { key_validity_url: None, public_key }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::serde::Base64;
+    use serde_json::{from_value as from_json_value, json};
+
+    use super::RoomThirdPartyInviteEventContent;
+
+    #[test]
+    fn public_keys_and_validity_urls() {
+        let content: RoomThirdPartyInviteEventContent = from_json_value(json!({
+            "display_name": "Alice Margatroid",
+            "key_validity_url": "https://magic.forest/verifykey",
+            "public_key": "AAECAwQ",
+            "public_keys": [
+                {
+                    "key_validity_url": "https://magic.forest/verifykey",
+                    "public_key": "AAECAwQ",
+                },
+                {
+                    "public_key": "BQYHCAk",
+                },
+            ],
+        }))
+        .unwrap();
+
+        let first_key = Base64::parse("AAECAwQ").unwrap();
+        let second_key = Base64::parse("BQYHCAk").unwrap();
+        assert_eq!(
+            content.all_public_keys().collect::<Vec<_>>(),
+            [&first_key, &first_key, &second_key]
+        );
+        assert_eq!(
+            content.key_validity_urls().collect::<Vec<_>>(),
+            ["https://magic.forest/verifykey", "https://magic.forest/verifykey"]
+        );
+
+        assert!(content.has_public_key(&second_key));
+        assert!(!content.has_public_key(&Base64::parse("CgsMDQ4").unwrap()));
+    }
+}
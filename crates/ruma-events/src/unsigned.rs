@@ -1,6 +1,7 @@
 use js_int::Int;
 use ruma_common::{
     serde::CanBeEmpty, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedTransactionId, OwnedUserId,
+    TransactionId,
 };
 use serde::{de::DeserializeOwned, Deserialize};
 
@@ -38,6 +39,14 @@ impl<C: MessageLikeEventContent> MessageLikeUnsigned<C> {
     pub fn new() -> Self {
         Self { age: None, transaction_id: None, relations: BundledMessageLikeRelations::default() }
     }
+
+    /// Whether this event was sent by this client with the given transaction ID.
+    ///
+    /// This can be used to match the local echo of an event with the event received from the
+    /// homeserver.
+    pub fn matches_transaction(&self, txn_id: &TransactionId) -> bool {
+        self.transaction_id.as_deref() == Some(txn_id)
+    }
 }
 
 impl<C: MessageLikeEventContent> Default for MessageLikeUnsigned<C> {
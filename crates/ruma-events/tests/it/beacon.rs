@@ -33,6 +33,14 @@ fn get_beacon_event_content_json() -> JsonValue {
     })
 }
 
+#[test]
+fn beacon_event_content_coordinates() {
+    let event_content = get_beacon_event_content();
+
+    assert_eq!(event_content.relates_to.event_id, "$beacon_info_event_id:example.com");
+    assert_eq!(event_content.coordinates(), Some((51.5008, 0.1247)));
+}
+
 #[test]
 fn beacon_event_content_serialization() {
     let event_content = get_beacon_event_content();
@@ -12,7 +12,7 @@ use ruma_events::{
     GlobalAccountDataEventType, MessageLikeEvent, MessageLikeEventType, OriginalMessageLikeEvent,
     OriginalStateEvent, OriginalSyncMessageLikeEvent, OriginalSyncStateEvent,
     RoomAccountDataEventType, StateEvent, StateEventType, SyncMessageLikeEvent, SyncStateEvent,
-    ToDeviceEventType,
+    TimelineEventType, ToDeviceEventType,
 };
 use serde_json::{from_value as from_json_value, json, Value as JsonValue};
 
@@ -282,3 +282,41 @@ fn serialize_and_deserialize_from_display_form() {
     serde_json_eq(StateEventType::RoomCreate, json!("m.room.create"));
     serde_json_eq(EphemeralRoomEventType::Typing, json!("m.typing"));
 }
+
+#[test]
+fn convert_timeline_event_type() {
+    assert_eq!(TimelineEventType::from(StateEventType::RoomMember), TimelineEventType::RoomMember);
+    assert_eq!(
+        StateEventType::try_from(TimelineEventType::RoomMember).unwrap(),
+        StateEventType::RoomMember
+    );
+    assert_eq!(
+        StateEventType::try_from(TimelineEventType::RoomMessage).unwrap_err(),
+        TimelineEventType::RoomMessage
+    );
+
+    assert_eq!(
+        MessageLikeEventType::try_from(TimelineEventType::RoomMessage).unwrap(),
+        MessageLikeEventType::RoomMessage
+    );
+    assert_eq!(
+        MessageLikeEventType::try_from(TimelineEventType::RoomMember).unwrap_err(),
+        TimelineEventType::RoomMember
+    );
+
+    let custom = TimelineEventType::from("dev.ruma.custom");
+    assert_eq!(StateEventType::try_from(custom).unwrap().to_string(), "dev.ruma.custom");
+}
+
+#[test]
+fn timeline_event_type_namespace() {
+    let official = TimelineEventType::RoomMessage;
+    assert!(official.is_official());
+    assert_eq!(official.namespace(), None);
+
+    let custom = TimelineEventType::from("com.example.thing");
+    assert!(!custom.is_official());
+    assert_eq!(custom.namespace(), Some("com.example"));
+
+    assert_eq!(TimelineEventType::from("com.example").namespace(), None);
+}
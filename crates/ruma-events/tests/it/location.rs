@@ -108,6 +108,24 @@ fn plain_content_deserialization() {
     assert_eq!(ev.ts, None);
 }
 
+#[test]
+fn location_coordinates() {
+    let content = LocationContent::new("geo:51.5008,0.1247;u=35".to_owned());
+    assert_eq!(content.coordinates(), Some((51.5008, 0.1247)));
+
+    let content = LocationContent::new("geo:-33.8,151.2,24.5".to_owned());
+    assert_eq!(content.coordinates(), Some((-33.8, 151.2)));
+
+    let content = LocationContent::new("geo:91,0".to_owned());
+    assert_eq!(content.coordinates(), None);
+
+    let content = LocationContent::new("geo:51.5008".to_owned());
+    assert_eq!(content.coordinates(), None);
+
+    let content = LocationContent::new("https://example.org".to_owned());
+    assert_eq!(content.coordinates(), None);
+}
+
 #[test]
 fn zoomlevel_deserialization_pass() {
     let json_data = json!({
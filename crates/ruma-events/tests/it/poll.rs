@@ -9,7 +9,7 @@ use ruma_events::{
     message::TextContentBlock,
     poll::{
         compile_poll_results, compile_unstable_poll_results,
-        end::PollEndEventContent,
+        end::{OriginalSyncPollEndEvent, PollEndEventContent},
         response::{OriginalSyncPollResponseEvent, PollResponseEventContent},
         start::{
             OriginalSyncPollStartEvent, PollAnswer, PollAnswers, PollAnswersError,
@@ -24,6 +24,7 @@ use ruma_events::{
             ReplacementUnstablePollStartEventContent, UnstablePollAnswer,
             UnstablePollStartContentBlock, UnstablePollStartEventContent,
         },
+        PollError,
     },
     relation::Reference,
     room::message::{Relation, RelationWithoutReplacement},
@@ -82,6 +83,59 @@ fn poll_answers_deserialization_not_enough() {
     assert_eq!(err.to_string(), PollAnswersError::NotEnoughValues.to_string());
 }
 
+#[test]
+fn poll_answers_with_generated_ids() {
+    let answers =
+        PollAnswers::with_generated_ids(["Yes", "No"].into_iter().map(TextContentBlock::plain))
+            .unwrap();
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[0].id, "1");
+    assert_eq!(answers[1].id, "2");
+
+    let err = PollAnswers::with_generated_ids(
+        (0..21).map(|i| TextContentBlock::plain(format!("Answer {i}"))),
+    )
+    .unwrap_err();
+    assert_eq!(err, PollAnswersError::TooManyValues);
+}
+
+#[test]
+fn start_content_validate() {
+    let answers =
+        PollAnswers::with_generated_ids(["Yes", "No"].into_iter().map(TextContentBlock::plain))
+            .unwrap();
+    let mut content = PollStartEventContent::with_plain_text(
+        "How's the weather?",
+        PollContentBlock::new(TextContentBlock::plain("How's the weather?"), answers),
+    );
+    content.validate().unwrap();
+
+    content.poll.max_selections = uint!(0);
+    assert_eq!(content.validate(), Err(PollError::InvalidMaxSelections));
+
+    content.poll.max_selections = uint!(1);
+    content.poll.question = TextContentBlock::plain(" ").into();
+    assert_eq!(content.validate(), Err(PollError::EmptyQuestion));
+}
+
+#[test]
+fn start_content_validate_duplicate_answer_ids() {
+    let content = PollStartEventContent::with_plain_text(
+        "How's the weather?",
+        PollContentBlock::new(
+            TextContentBlock::plain("How's the weather?"),
+            vec![
+                PollAnswer::new("same".to_owned(), TextContentBlock::plain("Not bad…")),
+                PollAnswer::new("same".to_owned(), TextContentBlock::plain("Fine.")),
+            ]
+            .try_into()
+            .unwrap(),
+        ),
+    );
+
+    assert_eq!(content.validate(), Err(PollError::DuplicateAnswerId("same".to_owned())));
+}
+
 #[test]
 fn start_content_serialization() {
     let event_content = PollStartEventContent::with_plain_text(
@@ -235,6 +289,71 @@ fn start_event_deserialization() {
     assert_matches!(message_event.content.relates_to, Some(Relation::Replacement(_)));
 }
 
+#[test]
+fn response_content_validate_against() {
+    let answers = PollAnswers::with_generated_ids(
+        ["Yes", "No", "Maybe"].into_iter().map(TextContentBlock::plain),
+    )
+    .unwrap();
+    let mut poll = PollStartEventContent::with_plain_text(
+        "Are you coming?",
+        PollContentBlock::new(TextContentBlock::plain("Are you coming?"), answers),
+    );
+    poll.poll.max_selections = uint!(2);
+
+    let response = PollResponseEventContent::new(
+        vec!["1".to_owned(), "3".to_owned()].into(),
+        owned_event_id!("$related_event:notareal.hs"),
+    );
+    response.validate_against(&poll).unwrap();
+
+    let mut response = PollResponseEventContent::new(
+        vec!["1".to_owned(), "4".to_owned(), "2".to_owned(), "3".to_owned()].into(),
+        owned_event_id!("$related_event:notareal.hs"),
+    );
+    assert_eq!(response.validate_against(&poll), Err(PollError::UnknownAnswerId("4".to_owned())));
+
+    // The vote is spoiled because an unknown answer remains after truncating.
+    response.sanitize_against(&poll);
+    assert!(response.selections.is_empty());
+    response.validate_against(&poll).unwrap();
+
+    // The unknown answer is dropped by the truncation.
+    let mut response = PollResponseEventContent::new(
+        vec!["1".to_owned(), "3".to_owned(), "4".to_owned()].into(),
+        owned_event_id!("$related_event:notareal.hs"),
+    );
+    response.sanitize_against(&poll);
+    assert_eq!(*response.selections, ["1", "3"]);
+}
+
+#[test]
+fn response_content_validate_against_over_selection() {
+    let answers =
+        PollAnswers::with_generated_ids(["Yes", "No"].into_iter().map(TextContentBlock::plain))
+            .unwrap();
+    let poll = PollStartEventContent::with_plain_text(
+        "Are you coming?",
+        PollContentBlock::new(TextContentBlock::plain("Are you coming?"), answers),
+    );
+
+    let mut response = PollResponseEventContent::new(
+        vec!["2".to_owned(), "1".to_owned()].into(),
+        owned_event_id!("$related_event:notareal.hs"),
+    );
+    assert_eq!(response.validate_against(&poll), Err(PollError::TooManySelections));
+
+    response.sanitize_against(&poll);
+    assert_eq!(*response.selections, ["2"]);
+
+    let mut response = PollResponseEventContent::new(
+        vec!["4".to_owned(), "1".to_owned()].into(),
+        owned_event_id!("$related_event:notareal.hs"),
+    );
+    response.sanitize_against(&poll);
+    assert!(response.selections.is_empty());
+}
+
 #[test]
 fn response_content_serialization() {
     let event_content = PollResponseEventContent::new(
@@ -353,6 +472,67 @@ fn end_content_with_results_serialization() {
     );
 }
 
+#[test]
+fn end_content_from_tally() {
+    let poll: OriginalSyncPollStartEvent = from_json_value(json!({
+        "type": "m.poll.start",
+        "sender": "@alice:localhost",
+        "event_id": "$poll_start_event_id",
+        "origin_server_ts": 1,
+        "content": {
+          "m.text": [{ "body": "Lunch?\n1. Pizza\n2. Salad" }],
+          "m.poll": {
+            "question": { "m.text": [{ "body": "Lunch?" }] },
+            "answers": [
+              { "m.id": "pizza", "m.text":  [{ "body": "Pizza" }] },
+              { "m.id": "salad", "m.text":  [{ "body": "Salad" }] },
+            ]
+          },
+        }
+    }))
+    .unwrap();
+
+    let mut responses = generate_poll_responses(0..3, &["salad"]);
+    responses.extend(generate_poll_responses(3..4, &["pizza"]));
+    let tally = compile_poll_results(&poll.content.poll, responses.iter().map(|r| r.data()), None);
+
+    let content = PollEndEventContent::from_tally(poll.event_id.clone(), &tally);
+    assert_eq!(
+        to_json_value(&content).unwrap(),
+        json!({
+            "m.text":  [{ "body": "The poll has closed. Top answer: salad" }],
+            "m.poll.results": {
+                "pizza": 1,
+                "salad": 3,
+            },
+            "m.relates_to": {
+                "rel_type": "m.reference",
+                "event_id": "$poll_start_event_id",
+            },
+        })
+    );
+
+    let end: OriginalSyncPollEndEvent = from_json_value(json!({
+        "type": "m.poll.end",
+        "sender": "@alice:localhost",
+        "event_id": "$poll_end_event_id",
+        "origin_server_ts": 2,
+        "content": to_json_value(&content).unwrap(),
+    }))
+    .unwrap();
+    assert!(end.is_sent_by_poll_creator(&poll));
+
+    let end: OriginalSyncPollEndEvent = from_json_value(json!({
+        "type": "m.poll.end",
+        "sender": "@bob:localhost",
+        "event_id": "$poll_end_event_id",
+        "origin_server_ts": 2,
+        "content": to_json_value(&content).unwrap(),
+    }))
+    .unwrap();
+    assert!(!end.is_sent_by_poll_creator(&poll));
+}
+
 #[test]
 fn end_event_deserialization() {
     let json_data = json!({
@@ -110,6 +110,38 @@ fn deserialize_redacted_any_room_sync() {
     assert_eq!(redacted.event_id, "$h29iv0s8:example.com");
 }
 
+#[test]
+fn unsigned_accessors() {
+    let redacted = json!({
+        "content": {},
+        "event_id": "$h29iv0s8:example.com",
+        "room_id": "!roomid:room.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "unsigned": unsigned(),
+        "type": "m.room.message",
+    });
+
+    let event = from_json_value::<AnyTimelineEvent>(redacted).unwrap();
+    assert_eq!(event.transaction_id(), None);
+    let redacted_because = event.redacted_because().unwrap();
+    assert_eq!(redacted_because.sender, "@carl:example.com");
+    assert_eq!(redacted_because.content.reason.as_deref(), Some("redacted because"));
+
+    let original = json!({
+        "content": { "msgtype": "m.text", "body": "Hello" },
+        "event_id": "$h29iv0s8:example.com",
+        "origin_server_ts": 1,
+        "sender": "@carl:example.com",
+        "unsigned": { "transaction_id": "txn1" },
+        "type": "m.room.message",
+    });
+
+    let event = from_json_value::<AnySyncTimelineEvent>(original).unwrap();
+    assert_eq!(event.transaction_id().unwrap(), "txn1");
+    assert_matches!(event.redacted_because(), None);
+}
+
 #[test]
 #[allow(deprecated)]
 fn deserialize_redacted_state_event() {
@@ -1,9 +1,11 @@
 use assert_matches2::assert_matches;
 use assign::assign;
-use ruma_common::owned_event_id;
+use js_int::uint;
+use ruma_common::{owned_event_id, owned_user_id};
 use ruma_events::{
     relation::{CustomRelation, InReplyTo, Replacement, Thread},
-    room::message::{MessageType, Relation, RoomMessageEventContent},
+    room::message::{MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent},
+    AnyMessageLikeEvent, MessageLikeEventType,
 };
 use serde_json::{
     from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
@@ -300,3 +302,118 @@ fn custom_serialize() {
         })
     );
 }
+
+#[test]
+fn relation_accessor() {
+    let content = from_json_value::<RoomMessageEventContent>(json!({
+        "msgtype": "m.text",
+        "body": "<text msg>",
+        "m.relates_to": {
+            "m.in_reply_to": {
+                "event_id": "$reply_target",
+            },
+        },
+    }))
+    .unwrap();
+    assert_matches!(content.relation(), Some(Relation::Reply { in_reply_to }));
+    assert_eq!(in_reply_to.event_id, "$reply_target");
+
+    let content = from_json_value::<RoomMessageEventContent>(json!({
+        "msgtype": "m.text",
+        "body": "* edited",
+        "m.new_content": {
+            "msgtype": "m.text",
+            "body": "edited",
+        },
+        "m.relates_to": {
+            "rel_type": "m.replace",
+            "event_id": "$edit_target",
+        },
+    }))
+    .unwrap();
+    assert_matches!(content.relation(), Some(Relation::Replacement(replacement)));
+    assert_eq!(replacement.event_id, "$edit_target");
+    assert_eq!(replacement.new_content.msgtype.body(), "edited");
+
+    let content = from_json_value::<RoomMessageEventContent>(json!({
+        "msgtype": "m.text",
+        "body": "In thread",
+        "m.relates_to": {
+            "rel_type": "m.thread",
+            "event_id": "$thread_root",
+            "is_falling_back": true,
+            "m.in_reply_to": {
+                "event_id": "$latest_in_thread",
+            },
+        },
+    }))
+    .unwrap();
+    assert_matches!(content.relation(), Some(Relation::Thread(thread)));
+    assert_eq!(thread.event_id, "$thread_root");
+
+    let content = RoomMessageEventContent::text_plain("No relation");
+    assert_matches!(content.relation(), None);
+}
+
+#[test]
+fn bundled_annotation_and_thread_deserialize() {
+    let json = json!({
+        "content": {
+            "body": "Thread root",
+            "msgtype": "m.text",
+        },
+        "event_id": "$root",
+        "origin_server_ts": 1_432_735_824_653_u64,
+        "sender": "@alice:example.org",
+        "type": "m.room.message",
+        "unsigned": {
+            "m.relations": {
+                "m.annotation": {
+                    "chunk": [
+                        { "type": "m.reaction", "key": "👍", "count": 3 },
+                        { "type": "m.reaction", "key": "🎉", "count": 1 },
+                    ],
+                },
+                "m.thread": {
+                    "latest_event": {
+                        "content": {
+                            "body": "Latest reply",
+                            "msgtype": "m.text",
+                            "m.relates_to": {
+                                "rel_type": "m.thread",
+                                "event_id": "$root",
+                            },
+                        },
+                        "event_id": "$latest",
+                        "origin_server_ts": 1_432_735_824_700_u64,
+                        "room_id": "!room:example.org",
+                        "sender": "@bob:example.org",
+                        "type": "m.room.message",
+                    },
+                    "count": 7,
+                    "current_user_participated": true,
+                },
+            },
+        },
+    });
+
+    let ev = from_json_value::<OriginalSyncRoomMessageEvent>(json).unwrap();
+    let relations = &ev.unsigned.relations;
+    assert!(!relations.is_empty());
+
+    let annotation = relations.annotation.as_deref().unwrap();
+    assert_eq!(annotation.chunk.len(), 2);
+    assert_eq!(annotation.chunk[0].event_type, MessageLikeEventType::Reaction);
+    assert_eq!(annotation.chunk[0].key, "👍");
+    assert_eq!(annotation.chunk[0].count, uint!(3));
+    assert_eq!(annotation.chunk[1].key, "🎉");
+    assert_eq!(annotation.chunk[1].count, uint!(1));
+
+    let thread = relations.thread.as_deref().unwrap();
+    assert_eq!(thread.count, uint!(7));
+    assert!(thread.current_user_participated);
+    let latest_event = thread.latest_event.deserialize().unwrap();
+    assert_matches!(latest_event, AnyMessageLikeEvent::RoomMessage(latest_event));
+    assert_eq!(latest_event.event_id(), "$latest");
+    assert_eq!(latest_event.sender(), owned_user_id!("@bob:example.org"));
+}
@@ -9,20 +9,24 @@ use ruma_common::{
 };
 use ruma_events::{
     key::verification::VerificationMethod,
+    relation::InReplyTo,
     room::{
         message::{
             AddMentions, AudioMessageEventContent, EmoteMessageEventContent,
-            FileMessageEventContent, ForwardThread, ImageMessageEventContent,
-            KeyVerificationRequestEventContent, MessageType, OriginalRoomMessageEvent,
-            OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread, RoomMessageEventContent,
-            TextMessageEventContent, VideoMessageEventContent,
+            FileMessageEventContent, FormattedBody, ForwardThread, ImageMessageEventContent,
+            KeyVerificationRequestEventContent, MediaCaption, MessageFormat, MessageType,
+            OriginalRoomMessageEvent, OriginalSyncRoomMessageEvent, Relation, ReplyWithinThread,
+            RoomMessageEventContent, ServerNoticeType, TextMessageEventContent,
+            VideoMessageEventContent,
         },
-        EncryptedFileInit, JsonWebKeyInit, MediaSource,
+        EncryptedFile, EncryptedFileInit, JsonWebKeyInit, MediaSource, ThumbnailInfo,
     },
     AnySyncTimelineEvent, Mentions, MessageLikeUnsigned,
 };
+use serde::Deserialize;
 use serde_json::{
-    from_value as from_json_value, json, to_value as to_json_value, Value as JsonValue,
+    from_value as from_json_value, json, to_string as to_json_string, to_value as to_json_value,
+    Value as JsonValue,
 };
 
 macro_rules! json_object {
@@ -75,6 +79,64 @@ fn custom_msgtype_deserialization() {
     assert_eq!(custom_event.data(), Cow::Owned(expected_json_data));
 }
 
+#[test]
+fn custom_msgtype_round_trip() {
+    let json = r#"{"a_field":[1,2],"body":"my custom message","custom":{"nested":true},"msgtype":"dev.ruma.custom","zzz":null}"#;
+
+    let custom_event: MessageType = serde_json::from_str(json).unwrap();
+    assert_eq!(custom_event.custom_msgtype(), Some("dev.ruma.custom"));
+    assert_eq!(custom_event.data().len(), 3);
+    assert_eq!(to_json_string(&custom_event).unwrap(), json);
+
+    assert_eq!(MessageType::text_plain("Hello").custom_msgtype(), None);
+}
+
+#[test]
+fn custom_msgtype_unsorted_serializes_sorted() {
+    let json = r#"{"zzz":null,"msgtype":"dev.ruma.custom","custom":{"nested":true},"body":"my custom message","a_field":[1,2]}"#;
+
+    let custom_event: MessageType = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        to_json_string(&custom_event).unwrap(),
+        r#"{"a_field":[1,2],"body":"my custom message","custom":{"nested":true},"msgtype":"dev.ruma.custom","zzz":null}"#
+    );
+}
+
+#[test]
+fn msgtype_deserialization_invalid_msgtype() {
+    let err = from_json_value::<MessageType>(json!({ "body": "no msgtype" })).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `msgtype`");
+
+    from_json_value::<MessageType>(json!({ "msgtype": 1, "body": "invalid msgtype" })).unwrap_err();
+    from_json_value::<MessageType>(json!({ "msgtype": "m.text" })).unwrap_err();
+}
+
+#[test]
+fn custom_msgtype_into_custom_type() {
+    #[derive(Debug, Deserialize)]
+    struct MyCustomMessageEventContent {
+        msgtype: String,
+        body: String,
+        custom_field: String,
+    }
+
+    let json_data = json!({
+        "msgtype": "my_custom_msgtype",
+        "body": "my custom message",
+        "custom_field": "baba",
+    });
+
+    let custom_event: MessageType = from_json_value(json_data).unwrap();
+    let content = custom_event.try_into_custom::<MyCustomMessageEventContent>().unwrap().unwrap();
+
+    assert_eq!(content.msgtype, "my_custom_msgtype");
+    assert_eq!(content.body, "my custom message");
+    assert_eq!(content.custom_field, "baba");
+
+    let text = MessageType::text_plain("Hello");
+    assert_matches!(text.try_into_custom::<MyCustomMessageEventContent>(), None);
+}
+
 #[test]
 fn text_msgtype_formatted_body_serialization() {
     let message_event_content =
@@ -91,6 +153,18 @@ fn text_msgtype_formatted_body_serialization() {
     );
 }
 
+#[test]
+fn formatted_body_html() {
+    let formatted = FormattedBody::html("<strong>Hello</strong>");
+
+    assert_eq!(formatted.format, MessageFormat::Html);
+    assert_eq!(formatted.format.as_str(), "org.matrix.custom.html");
+    assert!(formatted.is_html());
+
+    let formatted = FormattedBody { format: "org.example.format".into(), body: "Hello".to_owned() };
+    assert!(!formatted.is_html());
+}
+
 #[test]
 fn text_msgtype_plain_text_serialization() {
     let message_event_content =
@@ -190,9 +264,35 @@ line 2
 
 #[test]
 #[cfg(feature = "markdown")]
-fn markdown_detection() {
-    use ruma_events::room::message::FormattedBody;
+fn markdown_constructors() {
+    let text = "Testing **bold**";
 
+    let content = RoomMessageEventContent::text_markdown(text);
+    assert_eq!(
+        to_json_value(&content).unwrap(),
+        json!({
+            "body": text,
+            "formatted_body": "<p>Testing <strong>bold</strong></p>\n",
+            "format": "org.matrix.custom.html",
+            "msgtype": "m.text"
+        })
+    );
+
+    let content = RoomMessageEventContent::notice_markdown(text);
+    assert_eq!(
+        to_json_value(&content).unwrap(),
+        json!({
+            "body": text,
+            "formatted_body": "<p>Testing <strong>bold</strong></p>\n",
+            "format": "org.matrix.custom.html",
+            "msgtype": "m.notice"
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "markdown")]
+fn markdown_detection() {
     // No markdown
     let formatted_body = FormattedBody::markdown("A simple message.");
     assert_matches!(formatted_body, None);
@@ -213,8 +313,6 @@ fn markdown_detection() {
 #[test]
 #[cfg(feature = "markdown")]
 fn markdown_options() {
-    use ruma_events::room::message::FormattedBody;
-
     // Tables
     let formatted_body = FormattedBody::markdown(
         "|head1|head2|\n\
@@ -1116,6 +1214,55 @@ fn emote_msgtype_serialization() {
     );
 }
 
+#[test]
+fn notice_html_msgtype_serialization() {
+    let message_event_content =
+        RoomMessageEventContent::notice_html("Hello, World!", "Hello, <em>World</em>!");
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Hello, World!",
+            "msgtype": "m.notice",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "Hello, <em>World</em>!",
+        })
+    );
+}
+
+#[test]
+fn emote_html_msgtype_serialization() {
+    let message_event_content =
+        RoomMessageEventContent::emote_html("waves happily", "waves <strong>happily</strong>");
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "waves happily",
+            "msgtype": "m.emote",
+            "format": "org.matrix.custom.html",
+            "formatted_body": "waves <strong>happily</strong>",
+        })
+    );
+}
+
+#[test]
+fn server_notice_msgtype_serialization() {
+    let message_event_content = RoomMessageEventContent::server_notice(
+        "Usage limit reached",
+        ServerNoticeType::UsageLimitReached,
+    );
+
+    assert_eq!(
+        to_json_value(&message_event_content).unwrap(),
+        json!({
+            "body": "Usage limit reached",
+            "msgtype": "m.server_notice",
+            "server_notice_type": "m.server_notice.usage_limit_reached",
+        })
+    );
+}
+
 #[test]
 fn emote_msgtype_deserialization() {
     let json_data = json!({
@@ -1163,6 +1310,99 @@ fn video_msgtype_deserialization() {
     assert_eq!(url, "mxc://notareal.hs/file");
 }
 
+#[test]
+fn video_msgtype_info_round_trip() {
+    let json_data = json!({
+        "body": "Upload: my_video.mp4",
+        "url": "mxc://notareal.hs/file",
+        "info": {
+            "h": 1080,
+            "thumbnail_url": "mxc://notareal.hs/thumbnail",
+            "xyz.amorgan.blurhash": "LEHV6nWB2yk8pyo0adR*.7kCMdnj",
+            "dev.ruma.custom": { "foo": "bar" },
+        },
+        "msgtype": "m.video",
+    });
+
+    let content = from_json_value::<MessageType>(json_data.clone()).unwrap();
+    assert_matches!(&content, MessageType::Video(video));
+    let info = video.info.as_deref().unwrap();
+    assert_eq!(info.other.get("dev.ruma.custom"), Some(&json!({ "foo": "bar" })));
+    assert!(!info.other.contains_key("h"));
+    assert!(!info.other.contains_key("thumbnail_url"));
+
+    assert_eq!(to_json_value(&content).unwrap(), json_data);
+}
+
+#[test]
+fn video_msgtype_encrypted_with_thumbnail_serialization() {
+    fn encrypted_file(url: &str) -> EncryptedFile {
+        EncryptedFileInit {
+            url: url.into(),
+            key: JsonWebKeyInit {
+                kty: "oct".to_owned(),
+                key_ops: vec!["encrypt".to_owned(), "decrypt".to_owned()],
+                alg: "A256CTR".to_owned(),
+                k: Base64::parse("TLlG_OpX807zzQuuwv4QZGJ21_u7weemFGYJFszMn9A").unwrap(),
+                ext: true,
+            }
+            .into(),
+            iv: Base64::parse("S22dq3NAX8wAAAAAAAAAAA").unwrap(),
+            hashes: [(
+                "sha256".to_owned(),
+                Base64::parse("aWOHudBnDkJ9IwaR1Nd8XKoI7DOrqDTwt6xDPfVGN6Q").unwrap(),
+            )]
+            .into(),
+            v: "v2".to_owned(),
+        }
+        .into()
+    }
+
+    let mut thumbnail_info = ThumbnailInfo::new();
+    thumbnail_info.mimetype = Some("image/jpeg".to_owned());
+    thumbnail_info.size = Some(uint!(1024));
+
+    let content = VideoMessageEventContent::encrypted_with_thumbnail(
+        "Upload: my_video.mp4".to_owned(),
+        encrypted_file("mxc://notareal.hs/video"),
+        encrypted_file("mxc://notareal.hs/thumbnail"),
+        thumbnail_info,
+    );
+
+    let json = to_json_value(MessageType::Video(content)).unwrap();
+    assert_eq!(json["file"]["url"], "mxc://notareal.hs/video");
+    assert_eq!(json["info"]["thumbnail_file"]["url"], "mxc://notareal.hs/thumbnail");
+    assert_eq!(json["info"]["thumbnail_info"], json!({ "mimetype": "image/jpeg", "size": 1024 }));
+    assert_eq!(json["info"].get("thumbnail_url"), None);
+}
+
+#[test]
+fn media_caption() {
+    fn render_caption(m: &impl MediaCaption) -> Option<(&str, Option<&str>)> {
+        m.caption().map(|caption| (caption, m.formatted_caption().map(|f| f.body.as_str())))
+    }
+
+    let mut file =
+        FileMessageEventContent::plain("my_file.txt".to_owned(), mxc_uri!("mxc://a.b/c").into());
+    assert_eq!(file.filename(), "my_file.txt");
+    assert_eq!(render_caption(&file), None);
+
+    file.filename = Some("my_file.txt".to_owned());
+    assert_eq!(render_caption(&file), None);
+
+    let mut video = VideoMessageEventContent::plain(
+        "A *nice* video".to_owned(),
+        mxc_uri!("mxc://a.b/c").into(),
+    );
+    video.formatted = Some(FormattedBody::html("A <em>nice</em> video"));
+    assert_eq!(video.filename(), "A *nice* video");
+    assert_eq!(render_caption(&video), None);
+
+    video.filename = Some("video.mp4".to_owned());
+    assert_eq!(video.filename(), "video.mp4");
+    assert_eq!(render_caption(&video), Some(("A *nice* video", Some("A <em>nice</em> video"))));
+}
+
 #[test]
 #[allow(deprecated)]
 fn set_mentions() {
@@ -1287,3 +1527,105 @@ fn invalid_replacement() {
     assert_matches!(&data, Cow::Borrowed(_)); // data is stored in JSON form because it's invalid
     assert_eq!(JsonValue::Object(data.into_owned()), relation);
 }
+
+#[test]
+fn estimated_wire_size() {
+    let content = RoomMessageEventContent::text_plain("Hello, ünïcödé!");
+    assert_eq!(content.estimated_wire_size(), to_json_string(&content).unwrap().len());
+}
+
+#[test]
+fn split_body_on_char_boundaries() {
+    let mut content = RoomMessageEventContent::text_html("aéb€c😀d", "<p>aéb€c😀d</p>");
+    content.mentions = Some(Mentions::with_room_mention());
+
+    let split = content.split_body(4);
+    let bodies = split.iter().map(|content| content.body()).collect::<Vec<_>>();
+    assert_eq!(bodies, ["aéb", "€c", "😀", "d"]);
+    assert!(split.iter().all(|content| content.body().len() <= 4));
+    assert_matches!(&split[0].msgtype, MessageType::Text(text));
+    assert!(text.formatted.is_none());
+    assert!(split[0].mentions.is_some());
+    assert!(split[1].mentions.is_none());
+
+    // A character longer than the limit is put in its own message.
+    let content = RoomMessageEventContent::notice_plain("😀😀");
+    let split = content.split_body(2);
+    let bodies = split.iter().map(|content| content.body()).collect::<Vec<_>>();
+    assert_eq!(bodies, ["😀", "😀"]);
+    assert_matches!(&split[1].msgtype, MessageType::Notice(_));
+
+    // Short messages are not split.
+    let content = RoomMessageEventContent::text_plain("short");
+    assert_eq!(content.split_body(10).len(), 1);
+}
+
+#[test]
+fn preview() {
+    let content = RoomMessageEventContent::text_plain("Hello world");
+    assert_eq!(content.preview(20), "Hello world");
+    assert_eq!(content.preview(11), "Hello world");
+    assert_eq!(content.preview(6), "Hello…");
+
+    let content = RoomMessageEventContent::text_plain("Ünïcödé\ntëxt");
+    assert_eq!(content.preview(20), "Ünïcödé tëxt");
+    assert_eq!(content.preview(4), "Ünï…");
+
+    let content =
+        RoomMessageEventContent::new(MessageType::Emote(EmoteMessageEventContent::plain("waves")));
+    assert_eq!(content.preview(20), "* waves");
+
+    let mut content =
+        RoomMessageEventContent::text_plain("> <@alice:example.org> Hello\n\nHi Alice!");
+    content.relates_to =
+        Some(Relation::Reply { in_reply_to: InReplyTo::new(owned_event_id!("$replied_to")) });
+    assert_eq!(content.preview(20), "Hi Alice!");
+}
+
+#[test]
+fn as_command() {
+    let content = RoomMessageEventContent::text_plain("/join #room:example.org");
+    assert_eq!(content.as_command(), Some(("join", "#room:example.org")));
+
+    let content = RoomMessageEventContent::text_plain("/me  waves\nhello");
+    assert_eq!(content.as_command(), Some(("me", "waves\nhello")));
+
+    let content = RoomMessageEventContent::text_plain("/leave");
+    assert_eq!(content.as_command(), Some(("leave", "")));
+
+    let content = RoomMessageEventContent::text_plain("//escaped");
+    assert_eq!(content.as_command(), None);
+
+    let content = RoomMessageEventContent::text_plain("/ not a command");
+    assert_eq!(content.as_command(), None);
+
+    let content = RoomMessageEventContent::text_plain("Hello world");
+    assert_eq!(content.as_command(), None);
+
+    let content = RoomMessageEventContent::notice_plain("/join #room:example.org");
+    assert_eq!(content.as_command(), None);
+}
+
+#[test]
+fn unsigned_matches_transaction() {
+    let json_data = json!({
+        "content": {
+            "body": "Hello",
+            "msgtype": "m.text",
+        },
+        "event_id": "$143273582443PhrSn:example.org",
+        "origin_server_ts": 10_000,
+        "sender": "@example:example.org",
+        "type": "m.room.message",
+        "unsigned": {
+            "transaction_id": "m1476648745605.19",
+        },
+    });
+
+    let ev = from_json_value::<OriginalSyncRoomMessageEvent>(json_data).unwrap();
+    assert!(ev.unsigned.matches_transaction("m1476648745605.19".into()));
+    assert!(!ev.unsigned.matches_transaction("m1476648745605.20".into()));
+
+    let unsigned = MessageLikeUnsigned::<RoomMessageEventContent>::new();
+    assert!(!unsigned.matches_transaction("m1476648745605.19".into()));
+}
@@ -71,4 +71,57 @@ impl Response {
             // Return an iterator over just the values (`MatrixVersion`s)
             .into_values()
     }
+
+    /// Get the latest Matrix version supported by the server, if any is known.
+    pub fn max_known_version(&self) -> Option<MatrixVersion> {
+        self.known_versions().next_back()
+    }
+
+    /// Get the oldest Matrix version supported by the server, if any is known.
+    pub fn min_known_version(&self) -> Option<MatrixVersion> {
+        self.known_versions().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::api::MatrixVersion;
+    use serde_json::{json, to_value as to_json_value};
+
+    use super::{Response, METADATA};
+
+    #[test]
+    fn describe_metadata() {
+        assert_eq!(
+            to_json_value(METADATA.describe()).unwrap(),
+            json!({
+                "method": "GET",
+                "authentication": "None",
+                "rate_limited": false,
+                "unstable_paths": [],
+                "stable_paths": {
+                    "v1.1": "/_matrix/identity/versions",
+                },
+                "deprecated": null,
+                "removed": null,
+            })
+        );
+    }
+
+    #[test]
+    fn min_max_known_versions() {
+        // `r0.3.0` is not a version known by Ruma.
+        let response = Response::new(vec!["r0.3.0".to_owned(), "v1.1".to_owned()]);
+        assert_eq!(response.min_known_version(), Some(MatrixVersion::V1_1));
+        assert_eq!(response.max_known_version(), Some(MatrixVersion::V1_1));
+
+        let response =
+            Response::new(vec!["v1.2".to_owned(), "r0.6.1".to_owned(), "v1.1".to_owned()]);
+        assert_eq!(response.min_known_version(), Some(MatrixVersion::V1_0));
+        assert_eq!(response.max_known_version(), Some(MatrixVersion::V1_2));
+
+        let response = Response::new(vec!["r0.3.0".to_owned()]);
+        assert_eq!(response.min_known_version(), None);
+        assert_eq!(response.max_known_version(), None);
+    }
 }
@@ -133,13 +133,20 @@ impl Request {
                         request.body(),
                     );
 
-                    #serde_json::from_slice(match body {
-                        // If the request body is completely empty, pretend it is an empty JSON
-                        // object instead. This allows requests with only optional body parameters
-                        // to be deserialized in that case.
-                        [] => b"{}",
-                        b => b,
-                    })?
+                    match METADATA.body_format {
+                        #ruma_common::api::BodyFormat::Json => {
+                            #serde_json::from_slice(match body {
+                                // If the request body is completely empty, pretend it is an empty
+                                // JSON object instead. This allows requests with only optional
+                                // body parameters to be deserialized in that case.
+                                [] => b"{}",
+                                b => b,
+                            })?
+                        }
+                        #ruma_common::api::BodyFormat::FormUrlEncoded => {
+                            #serde_html_form::from_bytes(body)?
+                        }
+                    }
                 };
             }
         });
@@ -62,7 +62,14 @@ impl Request {
         // `application/json` content-type would be wrong. It may also cause problems with CORS
         // policies that don't allow the `Content-Type` header (for things such as `.well-known`
         // that are commonly handled by something else than a homeserver).
-        let mut header_kvs = if self.raw_body_field().is_some() || self.has_body_fields() {
+        let mut header_kvs = if self.has_body_fields() {
+            quote! {
+                req_headers.insert(
+                    #http::header::CONTENT_TYPE,
+                    #http::header::HeaderValue::from_static(METADATA.body_format.content_type()),
+                );
+            }
+        } else if self.raw_body_field().is_some() {
             quote! {
                 req_headers.insert(
                     #http::header::CONTENT_TYPE,
@@ -109,7 +116,16 @@ impl Request {
             let initializers = struct_init_fields(self.body_fields(), quote! { self });
 
             quote! {
-                #ruma_common::serde::json_to_buf(&RequestBody { #initializers })?
+                match METADATA.body_format {
+                    #ruma_common::api::BodyFormat::Json => {
+                        #ruma_common::serde::json_to_buf(&RequestBody { #initializers })?
+                    }
+                    #ruma_common::api::BodyFormat::FormUrlEncoded => {
+                        #ruma_common::serde::slice_to_buf(
+                            #serde_html_form::to_string(RequestBody { #initializers })?.as_bytes(),
+                        )
+                    }
+                }
             }
         } else {
             quote! { METADATA.empty_request_body::<T>() }
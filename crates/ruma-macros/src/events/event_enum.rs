@@ -621,7 +621,7 @@ fn expand_accessor_methods(
     });
 
     let maybe_redacted_accessors = maybe_redacted.then(|| {
-        let variants = variants.iter().map(|v| v.match_arm(quote! { Self }));
+        let variants: Vec<_> = variants.iter().map(|v| v.match_arm(quote! { Self })).collect();
 
         quote! {
             /// Returns this event's `transaction_id` from inside `unsigned`, if there is one.
@@ -637,6 +637,14 @@ fn expand_accessor_methods(
                     }
                 }
             }
+
+            /// Returns the redaction event from inside `unsigned`, if this event was redacted.
+            pub fn redacted_because(&self) -> Option<&#ruma_events::UnsignedRoomRedactionEvent> {
+                match self {
+                    #( #variants(event) => event.redacted_because(), )*
+                    Self::_Custom(event) => event.redacted_because(),
+                }
+            }
         }
     });
 
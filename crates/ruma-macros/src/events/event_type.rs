@@ -174,6 +174,21 @@ fn generate_enum(
             })
             .collect::<syn::Result<_>>()?;
 
+        let try_from_match_arms: Vec<_> = deduped
+            .iter()
+            .map(|e| {
+                let v = e.to_variant()?;
+                let timeline_var = v.match_arm(quote! { TimelineEventType });
+                let ident_var = v.ctor(quote! { Self });
+
+                Ok(if e.has_type_fragment() {
+                    quote! { #timeline_var (_s) => Ok(#ident_var (_s)) }
+                } else {
+                    quote! { #timeline_var => Ok(#ident_var) }
+                })
+            })
+            .collect::<syn::Result<_>>()?;
+
         Some(quote! {
             #[allow(deprecated)]
             impl ::std::convert::From<#ident> for TimelineEventType {
@@ -184,6 +199,23 @@ fn generate_enum(
                     }
                 }
             }
+
+            /// Convert a `TimelineEventType` to this type.
+            ///
+            /// Custom event types are always converted. For known event types that are not of
+            /// this kind, the original `TimelineEventType` is returned as the error.
+            #[allow(deprecated)]
+            impl ::std::convert::TryFrom<TimelineEventType> for #ident {
+                type Error = TimelineEventType;
+
+                fn try_from(s: TimelineEventType) -> ::std::result::Result<Self, Self::Error> {
+                    match s {
+                        #(#try_from_match_arms,)*
+                        TimelineEventType::_Custom(_s) => Ok(Self::_Custom(_s)),
+                        _ => Err(s),
+                    }
+                }
+            }
         })
     } else {
         None
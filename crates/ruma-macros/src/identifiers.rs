@@ -101,13 +101,13 @@ pub fn expand_id_zst(input: ItemStruct) -> syn::Result<TokenStream> {
 
             #[doc = #as_str_docs]
             #[inline]
-            pub fn as_str(&self) -> &str {
+            pub const fn as_str(&self) -> &str {
                 #as_str_impl
             }
 
             #[doc = #as_bytes_docs]
             #[inline]
-            pub fn as_bytes(&self) -> &[u8] {
+            pub const fn as_bytes(&self) -> &[u8] {
                 self.as_str().as_bytes()
             }
         }
@@ -243,7 +243,7 @@ pub fn mxc_uri(input: TokenStream) -> TokenStream {
     assert!(mxc_uri::validate(&id.value()).is_ok(), "Invalid mxc://");
 
     let output = quote! {
-        <&#dollar_crate::MxcUri as ::std::convert::From<&str>>::from(#id)
+        #dollar_crate::MxcUri::from_static(#id)
     };
 
     output.into()
@@ -143,11 +143,67 @@ fn resolve_deeper_event_set(c: &mut Criterion) {
     });
 }
 
+fn resolve_deeper_event_set_streamed(c: &mut Criterion) {
+    c.bench_function("resolve state of 10 events 3 conflicting with streamed auth chains", |b| {
+        let mut inner = INITIAL_EVENTS();
+        let ban = BAN_STATE_SET();
+
+        inner.extend(ban);
+        let store = TestStore(inner.clone());
+
+        let state_set_a = [
+            inner.get(&event_id("CREATE")).unwrap(),
+            inner.get(&event_id("IJR")).unwrap(),
+            inner.get(&event_id("IMA")).unwrap(),
+            inner.get(&event_id("IMB")).unwrap(),
+            inner.get(&event_id("IMC")).unwrap(),
+            inner.get(&event_id("MB")).unwrap(),
+            inner.get(&event_id("PA")).unwrap(),
+        ]
+        .iter()
+        .map(|ev| {
+            (ev.event_type().with_state_key(ev.state_key().unwrap()), ev.event_id().to_owned())
+        })
+        .collect::<StateMap<_>>();
+
+        let state_set_b = [
+            inner.get(&event_id("CREATE")).unwrap(),
+            inner.get(&event_id("IJR")).unwrap(),
+            inner.get(&event_id("IMA")).unwrap(),
+            inner.get(&event_id("IMB")).unwrap(),
+            inner.get(&event_id("IMC")).unwrap(),
+            inner.get(&event_id("IME")).unwrap(),
+            inner.get(&event_id("PA")).unwrap(),
+        ]
+        .iter()
+        .map(|ev| {
+            (ev.event_type().with_state_key(ev.state_key().unwrap()), ev.event_id().to_owned())
+        })
+        .collect::<StateMap<_>>();
+
+        b.iter(|| {
+            let state_sets = [&state_set_a, &state_set_b];
+            let _ = match state_res::Resolver::new(state_res::RoomVersion::V6)
+                .resolve_with_streamed_auth_chains(
+                    state_sets,
+                    state_sets.iter().map(|map| {
+                        store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap()
+                    }),
+                    |id| inner.get(id).map(Arc::clone),
+                ) {
+                Ok(state) => state,
+                Err(_) => panic!("resolution failed during benchmarking"),
+            };
+        });
+    });
+}
+
 criterion_group!(
     benches,
     lexico_topo_sort,
     resolution_shallow_auth_chain,
-    resolve_deeper_event_set
+    resolve_deeper_event_set,
+    resolve_deeper_event_set_streamed
 );
 
 criterion_main!(benches);
@@ -24,13 +24,50 @@ pub enum Error {
     #[error("Invalid PDU: {0}")]
     InvalidPdu(String),
 
+    /// The state resolution was aborted by [`ResolveHooks::should_abort`].
+    ///
+    /// [`ResolveHooks::should_abort`]: crate::ResolveHooks::should_abort
+    #[error("State resolution was aborted")]
+    Aborted,
+
     /// A custom error.
     #[error("{0}")]
-    Custom(Box<dyn std::error::Error>),
+    Custom(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// Represents the various reasons why an incoming PDU is rejected by [`validate_pdu`].
+///
+/// [`validate_pdu`]: crate::validate_pdu
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum PduValidationError {
+    /// The PDU exceeds the maximum size of a PDU.
+    #[error("PDU exceeds the maximum size of 65536 bytes")]
+    TooLarge,
+
+    /// A required field is missing from the PDU.
+    #[error("PDU is missing the `{0}` field")]
+    MissingField(&'static str),
+
+    /// An auth event of the PDU was not found.
+    #[error("auth event {0} of the PDU was not found")]
+    MissingAuthEvent(String),
+
+    /// The PDU does not pass the authorization rules.
+    #[error("PDU does not pass the authorization rules")]
+    Unauthorized,
+
+    /// The PDU is not valid JSON.
+    #[error(transparent)]
+    SerdeJson(#[from] JsonError),
+
+    /// An error occurred while checking the authorization rules.
+    #[error(transparent)]
+    Auth(#[from] Error),
 }
 
 impl Error {
-    pub fn custom<E: std::error::Error + 'static>(e: E) -> Self {
+    pub fn custom<E: std::error::Error + Send + Sync + 'static>(e: E) -> Self {
         Self::Custom(Box::new(e))
     }
 }
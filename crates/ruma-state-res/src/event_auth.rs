@@ -995,15 +995,9 @@ fn verify_third_party_invite(
         Err(_) => return false,
     };
 
-    // A list of public keys in the public_keys field
-    for key in tpid_ev.public_keys.unwrap_or_default() {
-        if key.public_key == decoded_invite_token {
-            return true;
-        }
-    }
-
-    // A single public key in the public_key field
-    tpid_ev.public_key == decoded_invite_token
+    // A single public key in the public_key field, or a list of public keys in the public_keys
+    // field
+    tpid_ev.has_public_key(&decoded_invite_token)
 }
 
 #[cfg(test)]
@@ -0,0 +1,64 @@
+//! Hooks to observe and cancel a state resolution.
+
+use crate::{Error, Result};
+
+/// A phase of the state resolution algorithm, as reported to [`ResolveHooks::on_phase`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub enum ResolvePhase {
+    /// The full conflicted set is computed from the conflicting state and the auth difference.
+    ConflictedSet,
+
+    /// The conflicted power events are sorted by reverse topological power ordering.
+    PowerSort,
+
+    /// The sorted power events are authorized against the unconflicted state.
+    PowerAuthCheck,
+
+    /// The remaining conflicted events are sorted by mainline ordering.
+    MainlineSort,
+
+    /// The remaining sorted events are authorized against the resolved power events.
+    FinalAuthCheck,
+}
+
+/// Callbacks called during a state resolution, set with [`Resolver::hooks`].
+///
+/// All methods have a default implementation that does nothing.
+///
+/// [`Resolver::hooks`]: crate::Resolver::hooks
+pub trait ResolveHooks {
+    /// Called when the state resolution enters the given phase.
+    fn on_phase(&mut self, _phase: ResolvePhase) {}
+
+    /// Whether the state resolution should be aborted.
+    ///
+    /// This is called between phases and regularly during each phase, e.g. before processing each
+    /// event. If it returns `true`, the state resolution stops and returns [`Error::Aborted`].
+    ///
+    /// [`Error::Aborted`]: crate::Error::Aborted
+    fn should_abort(&mut self) -> bool {
+        false
+    }
+}
+
+/// Hooks that do nothing, used by the state resolution functions without hooks.
+pub(crate) struct NoHooks;
+
+impl ResolveHooks for NoHooks {}
+
+/// Returns [`Error::Aborted`] if the state resolution should be aborted.
+pub(crate) fn check_abort(hooks: &mut dyn ResolveHooks) -> Result<()> {
+    if hooks.should_abort() {
+        return Err(Error::Aborted);
+    }
+
+    Ok(())
+}
+
+/// Checks whether the state resolution should be aborted, then reports the given phase.
+pub(crate) fn enter_phase(hooks: &mut dyn ResolveHooks, phase: ResolvePhase) -> Result<()> {
+    check_abort(hooks)?;
+    hooks.on_phase(phase);
+    Ok(())
+}
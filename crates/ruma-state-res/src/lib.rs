@@ -12,26 +12,61 @@ use ruma_events::{
     room::member::{MembershipState, RoomMemberEventContent},
     StateEventType, TimelineEventType,
 };
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::from_str as from_json_str;
 use tracing::{debug, info, trace, warn};
 
 mod error;
 pub mod event_auth;
+mod hooks;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pdu_validation;
 mod power_levels;
+mod resolver;
 pub mod room_version;
 mod state_event;
 #[cfg(test)]
 mod test_utils;
 
-pub use error::{Error, Result};
+pub use error::{Error, PduValidationError, Result};
 pub use event_auth::{auth_check, auth_types_for_event};
+use hooks::{check_abort, enter_phase, NoHooks};
+pub use hooks::{ResolveHooks, ResolvePhase};
+#[cfg(feature = "rayon")]
+pub use parallel::{resolve_many, ResolveJob};
+pub use pdu_validation::validate_pdu;
 use power_levels::PowerLevelsContentFields;
+pub use resolver::Resolver;
 pub use room_version::RoomVersion;
 pub use state_event::Event;
 
 /// A mapping of event type and state_key to some value `T`, usually an `EventId`.
 pub type StateMap<T> = HashMap<(StateEventType, String), T>;
 
+/// Returns the values of the given `StateMap`, usually event IDs, as a flat list.
+///
+/// The list is ordered by event type, then state key.
+pub fn to_event_id_list<Id>(state: &StateMap<Id>) -> Vec<&Id> {
+    state.iter().sorted_by(|(a, _), (b, _)| a.cmp(b)).map(|(_, id)| id).collect()
+}
+
+/// Serializes a `StateMap` as a flat array of its values, like the `state.events` of a sync
+/// response.
+///
+/// The array is ordered like with [`to_event_id_list()`]. To be used with
+/// `#[serde(serialize_with = "ruma_state_res::serialize_as_event_id_list")]`.
+pub fn serialize_as_event_id_list<Id, S>(
+    state: &StateMap<Id>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    Id: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(to_event_id_list(state))
+}
+
 /// Resolve sets of state events as they come in.
 ///
 /// Internally `StateResolution` builds a graph and an auth chain to allow for state conflict
@@ -52,6 +87,9 @@ pub type StateMap<T> = HashMap<(StateEventType, String), T>;
 ///
 /// The caller of `resolve` must ensure that all the events are from the same room. Although this
 /// function takes a `RoomId` it does not check that each event is part of the same room.
+///
+/// To use custom authorization rules, auth chains that are only iterated once, or to observe and
+/// abort the resolution, use a [`Resolver`].
 pub fn resolve<'a, E, SetIter>(
     room_version: &RoomVersionId,
     state_sets: impl IntoIterator<IntoIter = SetIter>,
@@ -62,6 +100,28 @@ where
     E: Event + Clone,
     E::Id: 'a,
     SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+{
+    resolve_inner(
+        || RoomVersion::new(room_version),
+        state_sets,
+        |hooks| get_auth_chain_diff(auth_chain_sets, hooks),
+        fetch_event,
+        &mut NoHooks,
+    )
+}
+
+fn resolve_inner<'a, E, SetIter, DiffIter>(
+    room_version: impl FnOnce() -> Result<RoomVersion>,
+    state_sets: impl IntoIterator<IntoIter = SetIter>,
+    auth_chain_diff: impl FnOnce(&mut dyn ResolveHooks) -> Result<DiffIter>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+    hooks: &mut dyn ResolveHooks,
+) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    E::Id: 'a,
+    SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+    DiffIter: Iterator<Item = E::Id>,
 {
     info!("State resolution starting");
 
@@ -79,9 +139,11 @@ where
     info!("conflicting events: {}", conflicting.len());
     debug!("{conflicting:?}");
 
+    enter_phase(hooks, ResolvePhase::ConflictedSet)?;
+
     // `all_conflicted` contains unique items
     // synapse says `full_set = {eid for eid in full_conflicted_set if eid in event_map}`
-    let all_conflicted: HashSet<_> = get_auth_chain_diff(auth_chain_sets)
+    let all_conflicted: HashSet<_> = auth_chain_diff(hooks)?
         .chain(conflicting.into_values().flatten())
         // Don't honor events we cannot "verify"
         .filter(|id| fetch_event(id.borrow()).is_some())
@@ -100,17 +162,26 @@ where
         .cloned()
         .collect::<Vec<_>>();
 
+    enter_phase(hooks, ResolvePhase::PowerSort)?;
+
     // Sort the control events based on power_level/clock/event_id and outgoing/incoming edges
     let sorted_control_levels =
-        reverse_topological_power_sort(control_events, &all_conflicted, &fetch_event)?;
+        reverse_topological_power_sort(control_events, &all_conflicted, &fetch_event, hooks)?;
 
     debug!("sorted control events: {}", sorted_control_levels.len());
     trace!("{sorted_control_levels:?}");
 
-    let room_version = RoomVersion::new(room_version)?;
+    let room_version = room_version()?;
+    enter_phase(hooks, ResolvePhase::PowerAuthCheck)?;
+
     // Sequentially auth check each control event.
-    let resolved_control =
-        iterative_auth_check(&room_version, &sorted_control_levels, clean.clone(), &fetch_event)?;
+    let resolved_control = iterative_auth_check(
+        &room_version,
+        &sorted_control_levels,
+        clean.clone(),
+        &fetch_event,
+        hooks,
+    )?;
 
     debug!("resolved control events: {}", resolved_control.len());
     trace!("{resolved_control:?}");
@@ -135,15 +206,21 @@ where
 
     debug!("power event: {power_event:?}");
 
-    let sorted_left_events = mainline_sort(&events_to_resolve, power_event.cloned(), &fetch_event)?;
+    enter_phase(hooks, ResolvePhase::MainlineSort)?;
+
+    let sorted_left_events =
+        mainline_sort(&events_to_resolve, power_event.cloned(), &fetch_event, hooks)?;
 
     trace!("events left, sorted: {sorted_left_events:?}");
 
+    enter_phase(hooks, ResolvePhase::FinalAuthCheck)?;
+
     let mut resolved_state = iterative_auth_check(
         &room_version,
         &sorted_left_events,
         resolved_control, // The control events are added to the final resolved state
         &fetch_event,
+        hooks,
     )?;
 
     // Add unconflicted state to the resolved state
@@ -188,7 +265,10 @@ where
 }
 
 /// Returns a Vec of deduped EventIds that appear in some chains but not others.
-fn get_auth_chain_diff<Id>(auth_chain_sets: Vec<HashSet<Id>>) -> impl Iterator<Item = Id>
+fn get_auth_chain_diff<Id>(
+    auth_chain_sets: Vec<HashSet<Id>>,
+    hooks: &mut dyn ResolveHooks,
+) -> Result<impl Iterator<Item = Id>>
 where
     Id: Eq + Hash,
 {
@@ -196,10 +276,46 @@ where
 
     let mut id_counts: HashMap<Id, usize> = HashMap::new();
     for id in auth_chain_sets.into_iter().flatten() {
+        check_abort(hooks)?;
         *id_counts.entry(id).or_default() += 1;
     }
 
-    id_counts.into_iter().filter_map(move |(id, count)| (count < num_sets).then_some(id))
+    Ok(id_counts.into_iter().filter_map(move |(id, count)| (count < num_sets).then_some(id)))
+}
+
+/// Returns the deduped EventIds that appear in some chains but not others, iterating over each
+/// chain only once.
+///
+/// Only the number of chains each event ID appears in is kept in memory, not the chains
+/// themselves.
+fn get_auth_chain_diff_streaming<Id, Chain>(
+    auth_chains: impl IntoIterator<Item = Chain>,
+    hooks: &mut dyn ResolveHooks,
+) -> Result<impl Iterator<Item = Id>>
+where
+    Id: Eq + Hash,
+    Chain: IntoIterator<Item = Id>,
+{
+    let mut num_sets = 0;
+
+    // For each event ID, the number of chains it appears in and the index of the last one, to
+    // only count an event ID once per chain.
+    let mut id_counts: HashMap<Id, (usize, usize)> = HashMap::new();
+    for (idx, chain) in auth_chains.into_iter().enumerate() {
+        num_sets += 1;
+
+        for id in chain {
+            check_abort(hooks)?;
+
+            let (count, last_idx) = id_counts.entry(id).or_insert((0, idx));
+            if *count == 0 || *last_idx != idx {
+                *count += 1;
+                *last_idx = idx;
+            }
+        }
+    }
+
+    Ok(id_counts.into_iter().filter_map(move |(id, (count, _))| (count < num_sets).then_some(id)))
 }
 
 /// Events are sorted from "earliest" to "latest".
@@ -213,12 +329,14 @@ fn reverse_topological_power_sort<E: Event>(
     events_to_sort: Vec<E::Id>,
     auth_diff: &HashSet<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    hooks: &mut dyn ResolveHooks,
 ) -> Result<Vec<E::Id>> {
     debug!("reverse topological sort of power events");
 
     let mut graph = HashMap::new();
     for event_id in events_to_sort {
-        add_event_and_auth_chain_to_graph(&mut graph, event_id, auth_diff, &fetch_event);
+        check_abort(hooks)?;
+        add_event_and_auth_chain_to_graph(&mut graph, event_id, auth_diff, &fetch_event, hooks)?;
 
         // TODO: if these functions are ever made async here
         // is a good place to yield every once in a while so other
@@ -228,6 +346,7 @@ fn reverse_topological_power_sort<E: Event>(
     // This is used in the `key_fn` passed to the lexico_topo_sort fn
     let mut event_to_pl = HashMap::new();
     for event_id in graph.keys() {
+        check_abort(hooks)?;
         let pl = get_power_level_for_sender(event_id.borrow(), &fetch_event)?;
         info!("{event_id} power level {pl}");
 
@@ -238,11 +357,15 @@ fn reverse_topological_power_sort<E: Event>(
         // tasks can make progress
     }
 
-    lexicographical_topological_sort(&graph, |event_id| {
-        let ev = fetch_event(event_id).ok_or_else(|| Error::NotFound("".into()))?;
-        let pl = *event_to_pl.get(event_id).ok_or_else(|| Error::NotFound("".into()))?;
-        Ok((pl, ev.origin_server_ts()))
-    })
+    lexicographical_topological_sort_inner(
+        &graph,
+        |event_id| {
+            let ev = fetch_event(event_id).ok_or_else(|| Error::NotFound("".into()))?;
+            let pl = *event_to_pl.get(event_id).ok_or_else(|| Error::NotFound("".into()))?;
+            Ok((pl, ev.origin_server_ts()))
+        },
+        hooks,
+    )
 }
 
 /// Sorts the event graph based on number of outgoing/incoming edges.
@@ -253,6 +376,18 @@ pub fn lexicographical_topological_sort<Id, F>(
     graph: &HashMap<Id, HashSet<Id>>,
     key_fn: F,
 ) -> Result<Vec<Id>>
+where
+    F: Fn(&EventId) -> Result<(Int, MilliSecondsSinceUnixEpoch)>,
+    Id: Clone + Eq + Ord + Hash + Borrow<EventId>,
+{
+    lexicographical_topological_sort_inner(graph, key_fn, &mut NoHooks)
+}
+
+fn lexicographical_topological_sort_inner<Id, F>(
+    graph: &HashMap<Id, HashSet<Id>>,
+    key_fn: F,
+    hooks: &mut dyn ResolveHooks,
+) -> Result<Vec<Id>>
 where
     F: Fn(&EventId) -> Result<(Int, MilliSecondsSinceUnixEpoch)>,
     Id: Clone + Eq + Ord + Hash + Borrow<EventId>,
@@ -284,6 +419,8 @@ where
     let mut zero_outdegree = Vec::new();
 
     for (node, edges) in graph {
+        check_abort(hooks)?;
+
         if edges.is_empty() {
             let (power_level, age) = key_fn(node.borrow())?;
             // The `Reverse` is because rusts `BinaryHeap` sorts largest -> smallest we need
@@ -307,6 +444,8 @@ where
     let mut sorted = vec![];
     // Destructure the `Reverse` and take the smallest `node` each time
     while let Some(Reverse(item)) = heap.pop() {
+        check_abort(hooks)?;
+
         let node = item.event_id;
 
         for &parent in reverse_graph.get(node).expect("EventId in heap is also in reverse_graph") {
@@ -386,6 +525,7 @@ fn iterative_auth_check<E: Event + Clone>(
     events_to_check: &[E::Id],
     unconflicted_state: StateMap<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    hooks: &mut dyn ResolveHooks,
 ) -> Result<StateMap<E::Id>> {
     info!("starting iterative auth check");
 
@@ -394,6 +534,8 @@ fn iterative_auth_check<E: Event + Clone>(
     let mut resolved_state = unconflicted_state;
 
     for event_id in events_to_check {
+        check_abort(hooks)?;
+
         let event = fetch_event(event_id.borrow())
             .ok_or_else(|| Error::NotFound(format!("Failed to find {event_id}")))?;
         let state_key = event
@@ -466,6 +608,7 @@ fn mainline_sort<E: Event>(
     to_sort: &[E::Id],
     resolved_power_level: Option<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
+    hooks: &mut dyn ResolveHooks,
 ) -> Result<Vec<E::Id>> {
     debug!("mainline sort of events");
 
@@ -477,6 +620,7 @@ fn mainline_sort<E: Event>(
     let mut mainline = vec![];
     let mut pl = resolved_power_level;
     while let Some(p) = pl {
+        check_abort(hooks)?;
         mainline.push(p.clone());
 
         let event = fetch_event(p.borrow())
@@ -504,6 +648,8 @@ fn mainline_sort<E: Event>(
 
     let mut order_map = HashMap::new();
     for ev_id in to_sort.iter() {
+        check_abort(hooks)?;
+
         if let Some(event) = fetch_event(ev_id.borrow()) {
             if let Ok(depth) = get_mainline_depth(Some(event), &mainline_map, &fetch_event) {
                 order_map.insert(
@@ -559,9 +705,12 @@ fn add_event_and_auth_chain_to_graph<E: Event>(
     event_id: E::Id,
     auth_diff: &HashSet<E::Id>,
     fetch_event: impl Fn(&EventId) -> Option<E>,
-) {
+    hooks: &mut dyn ResolveHooks,
+) -> Result<()> {
     let mut state = vec![event_id];
     while let Some(eid) = state.pop() {
+        check_abort(hooks)?;
+
         graph.entry(eid.clone()).or_default();
         // Prefer the store to event as the store filters dedups the events
         for aid in
@@ -577,6 +726,8 @@ fn add_event_and_auth_chain_to_graph<E: Event>(
             }
         }
     }
+
+    Ok(())
 }
 
 fn is_power_event_id<E: Event>(event_id: &EventId, fetch: impl Fn(&EventId) -> Option<E>) -> bool {
@@ -590,7 +741,14 @@ fn is_type_and_key(ev: impl Event, ev_type: &TimelineEventType, state_key: &str)
     ev.event_type() == ev_type && ev.state_key() == Some(state_key)
 }
 
-fn is_power_event(event: impl Event) -> bool {
+/// Whether the given event is a power event.
+///
+/// Per the spec, a [power event] is a state event with an empty state key and the type
+/// `m.room.power_levels`, `m.room.join_rules` or `m.room.create`, or an `m.room.member` event
+/// with a `leave` or `ban` membership whose sender is not the target user (i.e. a kick or a ban).
+///
+/// [power event]: https://spec.matrix.org/latest/rooms/v2/#definitions
+pub fn is_power_event(event: impl Event) -> bool {
     match event.event_type() {
         TimelineEventType::RoomPowerLevels
         | TimelineEventType::RoomJoinRules
@@ -608,6 +766,40 @@ fn is_power_event(event: impl Event) -> bool {
     }
 }
 
+/// Extracts the room version from the `m.room.create` event of a room.
+///
+/// Per the spec, rooms whose create event has no `room_version` are version 1.
+pub fn room_version_of(create_event: &impl Event) -> Result<RoomVersionId> {
+    #[derive(Deserialize)]
+    struct ExtractRoomVersion {
+        room_version: Option<RoomVersionId>,
+    }
+
+    if *create_event.event_type() != TimelineEventType::RoomCreate {
+        return Err(Error::InvalidPdu(format!(
+            "expected an m.room.create event, found {}",
+            create_event.event_type()
+        )));
+    }
+
+    let content: ExtractRoomVersion = from_json_str(create_event.content().get())?;
+    Ok(content.room_version.unwrap_or(RoomVersionId::V1))
+}
+
+/// Sorts the given events by depth, then by `origin_server_ts`, then by event ID.
+///
+/// Events without a [depth](Event::depth) are sorted before the others. This ordering is only a
+/// hint for displaying or debugging a timeline, it is not the ordering used by state resolution.
+pub fn sort_by_depth<E: Event>(events: &mut [E]) {
+    events.sort_by(|a, b| {
+        (a.depth(), a.origin_server_ts(), a.event_id()).cmp(&(
+            b.depth(),
+            b.origin_server_ts(),
+            b.event_id(),
+        ))
+    });
+}
+
 /// Convenience trait for adding event type plus state key to state maps.
 trait EventTypeExt {
     fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String);
@@ -621,7 +813,8 @@ impl EventTypeExt for StateEventType {
 
 impl EventTypeExt for TimelineEventType {
     fn with_state_key(self, state_key: impl Into<String>) -> (StateEventType, String) {
-        (self.to_string().into(), state_key.into())
+        let event_type = StateEventType::try_from(self).unwrap_or_else(|ty| ty.to_string().into());
+        (event_type, state_key.into())
     }
 }
 
@@ -641,25 +834,32 @@ mod tests {
         sync::Arc,
     };
 
+    use assert_matches2::assert_matches;
     use js_int::{int, uint};
     use maplit::{hashmap, hashset};
     use rand::seq::SliceRandom;
-    use ruma_common::{MilliSecondsSinceUnixEpoch, OwnedEventId, RoomVersionId};
+    use ruma_common::{EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, RoomVersionId};
     use ruma_events::{
-        room::join_rules::{JoinRule, RoomJoinRulesEventContent},
+        pdu::Pdu,
+        room::{
+            join_rules::{JoinRule, RoomJoinRulesEventContent},
+            member::{MembershipState, RoomMemberEventContent},
+        },
         StateEventType, TimelineEventType,
     };
+    use serde::Serialize;
     use serde_json::{json, value::to_raw_value as to_raw_json_value};
     use tracing::debug;
 
     use crate::{
         is_power_event,
         room_version::RoomVersion,
+        room_version_of, sort_by_depth,
         test_utils::{
             alice, bob, charlie, do_check, ella, event_id, member_content_ban, member_content_join,
             room_id, to_init_pdu_event, to_pdu_event, zara, PduEvent, TestStore, INITIAL_EVENTS,
         },
-        Event, EventTypeExt, StateMap,
+        Error, Event, EventTypeExt, ResolveHooks, ResolvePhase, Resolver, StateMap,
     };
 
     fn test_event_sort() {
@@ -680,17 +880,20 @@ mod tests {
             .map(|pdu| pdu.event_id.clone())
             .collect::<Vec<_>>();
 
-        let sorted_power_events =
-            crate::reverse_topological_power_sort(power_events, &auth_chain, |id| {
-                events.get(id).cloned()
-            })
-            .unwrap();
+        let sorted_power_events = crate::reverse_topological_power_sort(
+            power_events,
+            &auth_chain,
+            |id| events.get(id).cloned(),
+            &mut crate::NoHooks,
+        )
+        .unwrap();
 
         let resolved_power = crate::iterative_auth_check(
             &RoomVersion::V6,
             &sorted_power_events,
             HashMap::new(), // unconflicted events
             |id| events.get(id).cloned(),
+            &mut crate::NoHooks,
         )
         .expect("iterative auth check failed on resolved events");
 
@@ -702,9 +905,13 @@ mod tests {
         let power_level =
             resolved_power.get(&(StateEventType::RoomPowerLevels, "".to_owned())).cloned();
 
-        let sorted_event_ids =
-            crate::mainline_sort(&events_to_sort, power_level, |id| events.get(id).cloned())
-                .unwrap();
+        let sorted_event_ids = crate::mainline_sort(
+            &events_to_sort,
+            power_level,
+            |id| events.get(id).cloned(),
+            &mut crate::NoHooks,
+        )
+        .unwrap();
 
         assert_eq!(
             vec![
@@ -1065,6 +1272,333 @@ mod tests {
         assert_eq!(expected, resolved);
     }
 
+    #[test]
+    fn streamed_auth_chains() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let auth_chain_sets = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect::<Vec<_>>();
+
+        let resolved =
+            crate::resolve(&RoomVersionId::V6, &state_sets, auth_chain_sets.clone(), |id| {
+                ev_map.get(id).cloned()
+            })
+            .unwrap();
+        // Yield every event ID twice per chain to check that they are only counted once.
+        let streamed = Resolver::new(RoomVersion::V6)
+            .resolve_with_streamed_auth_chains(
+                &state_sets,
+                auth_chain_sets.iter().map(|chain| chain.iter().chain(chain).cloned()),
+                |id| ev_map.get(id).cloned(),
+            )
+            .unwrap();
+
+        assert_eq!(resolved, expected);
+        assert_eq!(streamed, resolved);
+    }
+
+    #[test]
+    fn resolve_with_custom_room_version() {
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+
+        // A room where knocking is allowed by the join rules, and Bob knocks in one of the forks.
+        let create = to_pdu_event::<&EventId>(
+            "CREATE",
+            alice(),
+            TimelineEventType::RoomCreate,
+            Some(""),
+            to_raw_json_value(&json!({ "creator": alice() })).unwrap(),
+            &[],
+            &[],
+        );
+        let alice_member = to_pdu_event(
+            "IMA",
+            alice(),
+            TimelineEventType::RoomMember,
+            Some(alice().as_str()),
+            member_content_join(),
+            &["CREATE"],
+            &["CREATE"],
+        );
+        let join_rules = to_pdu_event(
+            "IJR",
+            alice(),
+            TimelineEventType::RoomJoinRules,
+            Some(""),
+            to_raw_json_value(&RoomJoinRulesEventContent::new(JoinRule::Knock)).unwrap(),
+            &["CREATE", "IMA"],
+            &["IMA"],
+        );
+        let bob_knock = to_pdu_event(
+            "KNOCK",
+            bob(),
+            TimelineEventType::RoomMember,
+            Some(bob().as_str()),
+            to_raw_json_value(&RoomMemberEventContent::new(MembershipState::Knock)).unwrap(),
+            &["CREATE", "IJR"],
+            &["IJR"],
+        );
+
+        let store = TestStore::<PduEvent>(
+            [create, alice_member, join_rules, bob_knock]
+                .into_iter()
+                .map(|ev| (ev.event_id().to_owned(), ev))
+                .collect(),
+        );
+        let ev_map = store.0.clone();
+
+        let state_without_knock = [
+            (StateEventType::RoomCreate, ""),
+            (StateEventType::RoomMember, alice().as_str()),
+            (StateEventType::RoomJoinRules, ""),
+        ]
+        .into_iter()
+        .zip(["CREATE", "IMA", "IJR"])
+        .map(|((ty, key), id)| ((ty, key.to_owned()), event_id(id)))
+        .collect::<StateMap<_>>();
+        let mut state_with_knock = state_without_knock.clone();
+        state_with_knock.insert((StateEventType::RoomMember, bob().to_string()), event_id("KNOCK"));
+
+        let state_sets = [state_with_knock.clone(), state_without_knock.clone()];
+        let auth_chain_sets = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect::<Vec<_>>();
+
+        // A custom room version is rejected by `resolve`.
+        let custom_version = RoomVersionId::try_from("org.example.custom").unwrap();
+        crate::resolve(&custom_version, &state_sets, auth_chain_sets.clone(), |id| {
+            ev_map.get(id).cloned()
+        })
+        .unwrap_err();
+
+        // Room version 6 doesn't allow knocking, so the knock is rejected.
+        let resolved = Resolver::new(RoomVersion::V6)
+            .resolve(&state_sets, auth_chain_sets.clone(), |id| ev_map.get(id).cloned())
+            .unwrap();
+        assert_eq!(resolved, state_without_knock);
+
+        // With the custom rules, the knock is accepted.
+        let mut room_version = RoomVersion::V6;
+        room_version.allow_knocking = true;
+        let resolved = Resolver::new(room_version)
+            .resolve(&state_sets, auth_chain_sets, |id| ev_map.get(id).cloned())
+            .unwrap();
+        assert_eq!(resolved, state_with_knock);
+    }
+
+    #[test]
+    fn resolver_hooks_report_phases_and_abort() {
+        #[derive(Default)]
+        struct RecordingHooks {
+            phases: Vec<ResolvePhase>,
+            // Abort at the n-th call to `should_abort` during the given phase.
+            abort_at: Option<(ResolvePhase, usize)>,
+            checks_in_phase: usize,
+        }
+
+        impl ResolveHooks for RecordingHooks {
+            fn on_phase(&mut self, phase: ResolvePhase) {
+                self.phases.push(phase);
+                self.checks_in_phase = 0;
+            }
+
+            fn should_abort(&mut self) -> bool {
+                self.checks_in_phase += 1;
+                self.abort_at.is_some_and(|(phase, n)| {
+                    self.phases.last() == Some(&phase) && self.checks_in_phase == n
+                })
+            }
+        }
+
+        let _ =
+            tracing::subscriber::set_default(tracing_subscriber::fmt().with_test_writer().finish());
+
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let ev_map = store.0.clone();
+        let state_sets = [state_at_bob, state_at_charlie];
+        let auth_chain_sets = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut hooks = RecordingHooks::default();
+        let resolved = Resolver::new(RoomVersion::V6)
+            .hooks(&mut hooks)
+            .resolve(&state_sets, auth_chain_sets.clone(), |id| ev_map.get(id).cloned())
+            .unwrap();
+
+        assert_eq!(resolved, expected);
+        assert_eq!(
+            hooks.phases,
+            [
+                ResolvePhase::ConflictedSet,
+                ResolvePhase::PowerSort,
+                ResolvePhase::PowerAuthCheck,
+                ResolvePhase::MainlineSort,
+                ResolvePhase::FinalAuthCheck,
+            ]
+        );
+
+        // Abort at the boundary between two phases.
+        let mut hooks = RecordingHooks {
+            abort_at: Some((ResolvePhase::ConflictedSet, 1)),
+            ..Default::default()
+        };
+        let err = Resolver::new(RoomVersion::V6)
+            .hooks(&mut hooks)
+            .resolve(&state_sets, auth_chain_sets.clone(), |id| ev_map.get(id).cloned())
+            .unwrap_err();
+
+        assert_matches!(err, Error::Aborted);
+        assert_eq!(hooks.phases, [ResolvePhase::ConflictedSet]);
+
+        // Abort when leaving the sort of the power events, with streamed auth chains.
+        let mut hooks =
+            RecordingHooks { abort_at: Some((ResolvePhase::PowerSort, 1)), ..Default::default() };
+        let err = Resolver::new(RoomVersion::V6)
+            .hooks(&mut hooks)
+            .resolve_with_streamed_auth_chains(&state_sets, auth_chain_sets, |id| {
+                ev_map.get(id).cloned()
+            })
+            .unwrap_err();
+
+        assert_matches!(err, Error::Aborted);
+        assert_eq!(hooks.phases, [ResolvePhase::ConflictedSet, ResolvePhase::PowerSort]);
+    }
+
+    #[test]
+    fn sorts_abort_midway() {
+        struct AbortAfter {
+            checks: usize,
+            abort_at: usize,
+        }
+
+        impl ResolveHooks for AbortAfter {
+            fn should_abort(&mut self) -> bool {
+                self.checks += 1;
+                self.checks == self.abort_at
+            }
+        }
+
+        let events = INITIAL_EVENTS();
+        let power_events = events
+            .values()
+            .filter(|&pdu| is_power_event(&**pdu))
+            .map(|pdu| pdu.event_id.clone())
+            .collect::<Vec<_>>();
+        let auth_chain: HashSet<OwnedEventId> = HashSet::new();
+
+        // Without aborting, the power sort checks several times whether it should abort.
+        let mut hooks = AbortAfter { checks: 0, abort_at: 0 };
+        let sorted_power_events = crate::reverse_topological_power_sort(
+            power_events.clone(),
+            &auth_chain,
+            |id| events.get(id).cloned(),
+            &mut hooks,
+        )
+        .unwrap();
+        assert!(hooks.checks > 2);
+
+        let mut hooks = AbortAfter { checks: 0, abort_at: 2 };
+        let err = crate::reverse_topological_power_sort(
+            power_events,
+            &auth_chain,
+            |id| events.get(id).cloned(),
+            &mut hooks,
+        )
+        .unwrap_err();
+        assert_matches!(err, Error::Aborted);
+        assert_eq!(hooks.checks, 2);
+
+        let power_level = sorted_power_events
+            .iter()
+            .rev()
+            .find(|id| events[*id].event_type() == &TimelineEventType::RoomPowerLevels)
+            .cloned();
+        let events_to_sort = events.keys().cloned().collect::<Vec<_>>();
+        let mut hooks = AbortAfter { checks: 0, abort_at: 2 };
+        let err = crate::mainline_sort(
+            &events_to_sort,
+            power_level,
+            |id| events.get(id).cloned(),
+            &mut hooks,
+        )
+        .unwrap_err();
+        assert_matches!(err, Error::Aborted);
+        assert_eq!(hooks.checks, 2);
+
+        // The auth difference computed during the `ConflictedSet` phase can also be aborted.
+        let auth_chains = vec![
+            events.keys().cloned().collect::<HashSet<_>>(),
+            events.keys().take(1).cloned().collect::<HashSet<_>>(),
+        ];
+
+        let mut hooks = AbortAfter { checks: 0, abort_at: 2 };
+        let err = crate::get_auth_chain_diff(auth_chains.clone(), &mut hooks).err().unwrap();
+        assert_matches!(err, Error::Aborted);
+        assert_eq!(hooks.checks, 2);
+
+        let mut hooks = AbortAfter { checks: 0, abort_at: 2 };
+        let err = crate::get_auth_chain_diff_streaming(auth_chains, &mut hooks).err().unwrap();
+        assert_matches!(err, Error::Aborted);
+        assert_eq!(hooks.checks, 2);
+    }
+
+    #[test]
+    fn auth_chain_diff_strategies() {
+        let chains = vec![
+            hashset![event_id("A"), event_id("B"), event_id("C")],
+            hashset![event_id("A"), event_id("C"), event_id("D")],
+            hashset![event_id("A"), event_id("E")],
+        ];
+
+        let diff = crate::get_auth_chain_diff(chains.clone(), &mut crate::NoHooks)
+            .unwrap()
+            .collect::<HashSet<_>>();
+        let streamed = crate::get_auth_chain_diff_streaming(chains, &mut crate::NoHooks)
+            .unwrap()
+            .collect::<HashSet<_>>();
+
+        assert_eq!(diff, hashset![event_id("B"), event_id("C"), event_id("D"), event_id("E")]);
+        assert_eq!(streamed, diff);
+    }
+
+    #[test]
+    fn state_map_as_event_id_list() {
+        #[derive(Serialize)]
+        struct SyncState {
+            #[serde(serialize_with = "crate::serialize_as_event_id_list")]
+            events: StateMap<OwnedEventId>,
+        }
+
+        let events = hashmap! {
+            (StateEventType::RoomMember, alice().to_string()) => event_id("IMA"),
+            (StateEventType::RoomCreate, String::new()) => event_id("CREATE"),
+            (StateEventType::RoomMember, bob().to_string()) => event_id("IMB"),
+        };
+
+        assert_eq!(
+            crate::to_event_id_list(&events),
+            [&event_id("CREATE"), &event_id("IMA"), &event_id("IMB")]
+        );
+        assert_eq!(
+            serde_json::to_value(SyncState { events }).unwrap(),
+            json!({ "events": ["$CREATE:foo", "$IMA:foo", "$IMB:foo"] })
+        );
+    }
+
     #[test]
     fn test_lexicographical_sort() {
         let _ =
@@ -1265,4 +1799,110 @@ mod tests {
         .map(|ev| (ev.event_id.clone(), ev))
         .collect()
     }
+
+    #[test]
+    fn room_version_of_create_event() {
+        let create_event = to_init_pdu_event(
+            "CREATE",
+            alice(),
+            TimelineEventType::RoomCreate,
+            Some(""),
+            to_raw_json_value(&json!({ "creator": alice(), "room_version": "6" })).unwrap(),
+        );
+        assert_eq!(room_version_of(&create_event).unwrap(), RoomVersionId::V6);
+
+        let create_event = to_init_pdu_event(
+            "CREATE",
+            alice(),
+            TimelineEventType::RoomCreate,
+            Some(""),
+            to_raw_json_value(&json!({ "creator": alice() })).unwrap(),
+        );
+        assert_eq!(room_version_of(&create_event).unwrap(), RoomVersionId::V1);
+
+        let member_event = to_init_pdu_event(
+            "IMA",
+            alice(),
+            TimelineEventType::RoomMember,
+            Some(alice().as_str()),
+            member_content_join(),
+        );
+        room_version_of(&member_event).unwrap_err();
+    }
+
+    #[test]
+    fn sort_events_by_depth() {
+        let with_depth = |id: &str, depth| {
+            let mut ev = Arc::try_unwrap(to_pdu_event::<&str>(
+                id,
+                alice(),
+                TimelineEventType::RoomMessage,
+                None,
+                to_raw_json_value(&json!({})).unwrap(),
+                &[],
+                &[],
+            ))
+            .unwrap();
+            match &mut ev.rest {
+                Pdu::RoomV3Pdu(pdu) => pdu.depth = depth,
+                _ => unreachable!(),
+            }
+            ev
+        };
+
+        // Created in timestamp order, so events with the same depth keep that order.
+        let mut events = vec![
+            with_depth("A", uint!(3)),
+            with_depth("B", uint!(1)),
+            with_depth("C", uint!(2)),
+            with_depth("D", uint!(1)),
+        ];
+        sort_by_depth(&mut events);
+
+        let ids = events.iter().map(|ev| ev.event_id().as_str()).collect::<Vec<_>>();
+        assert_eq!(ids, ["$B:foo", "$D:foo", "$C:foo", "$A:foo"]);
+        assert_eq!(
+            events.iter().map(|ev| ev.depth()).collect::<Vec<_>>(),
+            [Some(1), Some(1), Some(2), Some(3)]
+        );
+    }
+
+    #[test]
+    fn power_event_classification() {
+        let power_levels = to_init_pdu_event(
+            "PL",
+            alice(),
+            TimelineEventType::RoomPowerLevels,
+            Some(""),
+            to_raw_json_value(&json!({ "users": { alice(): 100 } })).unwrap(),
+        );
+        assert!(is_power_event(&*power_levels));
+
+        let message = to_init_pdu_event(
+            "MSG",
+            alice(),
+            TimelineEventType::RoomMessage,
+            None,
+            to_raw_json_value(&json!({ "msgtype": "m.text", "body": "hi" })).unwrap(),
+        );
+        assert!(!is_power_event(&*message));
+
+        let kick = to_init_pdu_event(
+            "KICK",
+            alice(),
+            TimelineEventType::RoomMember,
+            Some(bob().as_str()),
+            to_raw_json_value(&json!({ "membership": "leave" })).unwrap(),
+        );
+        assert!(is_power_event(&*kick));
+
+        let leave = to_init_pdu_event(
+            "LEAVE",
+            bob(),
+            TimelineEventType::RoomMember,
+            Some(bob().as_str()),
+            to_raw_json_value(&json!({ "membership": "leave" })).unwrap(),
+        );
+        assert!(!is_power_event(&*leave));
+    }
 }
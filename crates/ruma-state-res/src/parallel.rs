@@ -0,0 +1,113 @@
+//! Resolution of the state of several rooms in parallel.
+
+use std::collections::HashSet;
+
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use ruma_common::{EventId, RoomVersionId};
+
+use crate::{resolve, Event, Result, StateMap};
+
+/// A self-contained state resolution, to be run with [`resolve_many`].
+///
+/// The arguments have the same meaning as those of [`resolve`].
+#[derive(Debug)]
+#[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
+pub struct ResolveJob<Id, F> {
+    /// The version of the room.
+    pub room_version: RoomVersionId,
+
+    /// The incoming state to resolve.
+    pub state_sets: Vec<StateMap<Id>>,
+
+    /// The full recursive set of `auth_events` for each event in the `state_sets`.
+    pub auth_chain_sets: Vec<HashSet<Id>>,
+
+    /// The closure used to fetch the events of the room.
+    pub fetch_event: F,
+}
+
+impl<Id, F> ResolveJob<Id, F> {
+    /// Creates a new `ResolveJob` with the given room version, state sets, auth chain sets and
+    /// closure to fetch events.
+    pub fn new(
+        room_version: RoomVersionId,
+        state_sets: Vec<StateMap<Id>>,
+        auth_chain_sets: Vec<HashSet<Id>>,
+        fetch_event: F,
+    ) -> Self {
+        Self { room_version, state_sets, auth_chain_sets, fetch_event }
+    }
+}
+
+/// Run several independent state resolutions in parallel.
+///
+/// Each job is resolved with [`resolve`] on the global rayon thread pool. The results are
+/// returned in the same order as the jobs.
+pub fn resolve_many<E, F>(
+    jobs: impl IntoIterator<Item = ResolveJob<E::Id, F>>,
+) -> Vec<Result<StateMap<E::Id>>>
+where
+    E: Event + Clone,
+    E::Id: Send,
+    F: Fn(&EventId) -> Option<E> + Send,
+{
+    jobs.into_iter().collect::<Vec<_>>().into_par_iter().map(run_job).collect()
+}
+
+fn run_job<E, F>(job: ResolveJob<E::Id, F>) -> Result<StateMap<E::Id>>
+where
+    E: Event + Clone,
+    F: Fn(&EventId) -> Option<E>,
+{
+    let ResolveJob { room_version, state_sets, auth_chain_sets, fetch_event } = job;
+    resolve(&room_version, &state_sets, auth_chain_sets, fetch_event)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use maplit::hashmap;
+    use ruma_common::{EventId, OwnedEventId, RoomVersionId};
+
+    use super::{resolve_many, ResolveJob};
+    use crate::{
+        test_utils::{room_id, PduEvent, TestStore},
+        StateMap,
+    };
+
+    fn resolve_job(
+        room_version: RoomVersionId,
+    ) -> (
+        ResolveJob<OwnedEventId, impl Fn(&EventId) -> Option<Arc<PduEvent>>>,
+        StateMap<OwnedEventId>,
+    ) {
+        let mut store = TestStore::<PduEvent>(hashmap! {});
+        let (state_at_bob, state_at_charlie, expected) = store.set_up();
+
+        let state_sets = vec![state_at_bob, state_at_charlie];
+        let auth_chain_sets = state_sets
+            .iter()
+            .map(|map| store.auth_event_ids(room_id(), map.values().cloned().collect()).unwrap())
+            .collect();
+        let ev_map = store.0;
+
+        let job =
+            ResolveJob::new(room_version, state_sets, auth_chain_sets, move |id: &EventId| {
+                ev_map.get(id).cloned()
+            });
+        (job, expected)
+    }
+
+    #[test]
+    fn resolve_independent_rooms() {
+        let (job_a, expected_a) = resolve_job(RoomVersionId::V2);
+        let (job_b, expected_b) = resolve_job(RoomVersionId::V6);
+
+        let results = resolve_many([job_a, job_b]);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), &expected_a);
+        assert_eq!(results[1].as_ref().unwrap(), &expected_b);
+    }
+}
@@ -0,0 +1,194 @@
+use std::{borrow::Borrow, collections::BTreeMap};
+
+use ruma_common::EventId;
+use ruma_events::StateEventType;
+use serde::de::IgnoredAny;
+use serde_json::{from_str as from_json_str, value::RawValue as RawJsonValue};
+
+use crate::{
+    auth_check, auth_types_for_event,
+    room_version::{EventFormatVersion, RoomVersion},
+    Event, PduValidationError, StateMap,
+};
+
+/// The maximum size of a PDU, in bytes.
+const MAX_PDU_BYTES: usize = 65_536;
+
+/// The fields that must be present in every PDU.
+const REQUIRED_FIELDS: &[&str] = &[
+    "auth_events",
+    "content",
+    "depth",
+    "hashes",
+    "origin_server_ts",
+    "prev_events",
+    "room_id",
+    "sender",
+    "signatures",
+    "type",
+];
+
+/// Validate an incoming PDU before accepting it in a room.
+///
+/// This runs the following checks of the [receive path], in order:
+///
+/// * the PDU does not exceed the maximum size of 65536 bytes,
+/// * all the fields required by the event format of the room version are present,
+/// * all the `auth_events` of the PDU can be fetched,
+/// * the PDU passes the authorization rules against the given `state`, which is the state of the
+///   room before the PDU.
+///
+/// The signatures and hashes of the PDU are not checked, this must be done separately, for
+/// example with `ruma-signatures`.
+///
+/// ## Arguments
+///
+/// * `pdu` - The PDU to validate.
+///
+/// * `raw` - The raw JSON of the PDU, as it was received.
+///
+/// * `room_version` - The version of the room the PDU was sent to.
+///
+/// * `state` - The state of the room before the PDU.
+///
+/// * `fetch_event` - The closure used to fetch the auth events and the events in `state`.
+///
+/// [receive path]: https://spec.matrix.org/latest/server-server-api/#checks-performed-on-receipt-of-a-pdu
+pub fn validate_pdu<E: Event>(
+    pdu: &impl Event,
+    raw: &RawJsonValue,
+    room_version: &RoomVersion,
+    state: &StateMap<E::Id>,
+    fetch_event: impl Fn(&EventId) -> Option<E>,
+) -> Result<(), PduValidationError> {
+    if raw.get().len() > MAX_PDU_BYTES {
+        return Err(PduValidationError::TooLarge);
+    }
+
+    let fields: BTreeMap<String, IgnoredAny> = from_json_str(raw.get())?;
+    let event_id_field =
+        matches!(room_version.event_format, EventFormatVersion::V1).then_some("event_id");
+    if let Some(field) =
+        REQUIRED_FIELDS.iter().copied().chain(event_id_field).find(|f| !fields.contains_key(*f))
+    {
+        return Err(PduValidationError::MissingField(field));
+    }
+
+    if let Some(auth_event_id) = pdu.auth_events().find(|id| fetch_event((*id).borrow()).is_none())
+    {
+        return Err(PduValidationError::MissingAuthEvent(auth_event_id.to_string()));
+    }
+
+    let fetch_state = |event_type: &StateEventType, state_key: &str| {
+        state
+            .get(&(event_type.clone(), state_key.to_owned()))
+            .and_then(|event_id| fetch_event(event_id.borrow()))
+    };
+
+    let auth_types =
+        auth_types_for_event(pdu.event_type(), pdu.sender(), pdu.state_key(), pdu.content())?;
+    let current_third_party_invite = auth_types
+        .iter()
+        .filter(|(event_type, _)| *event_type == StateEventType::RoomThirdPartyInvite)
+        .find_map(|(event_type, state_key)| fetch_state(event_type, state_key));
+
+    if auth_check(room_version, pdu, current_third_party_invite, fetch_state)? {
+        Ok(())
+    } else {
+        Err(PduValidationError::Unauthorized)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, sync::Arc};
+
+    use assert_matches2::assert_matches;
+    use ruma_common::OwnedEventId;
+    use ruma_events::TimelineEventType;
+    use serde_json::{json, value::to_raw_value as to_raw_json_value};
+
+    use super::validate_pdu;
+    use crate::{
+        test_utils::{
+            alice, ella, event_id, member_content_join, to_pdu_event, zara, PduEvent,
+            INITIAL_EVENTS,
+        },
+        Event, EventTypeExt, PduValidationError, RoomVersion, StateMap,
+    };
+
+    fn room_state(events: &HashMap<OwnedEventId, Arc<PduEvent>>) -> StateMap<OwnedEventId> {
+        ["CREATE", "IMA", "IPOWER", "IJR", "IMB", "IMC"]
+            .into_iter()
+            .map(|id| {
+                let event = &events[&event_id(id)];
+                let key = event.event_type().with_state_key(event.state_key().unwrap());
+                (key, event.event_id().clone())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_join() {
+        let events = INITIAL_EVENTS();
+        let state = room_state(&events);
+
+        let join = to_pdu_event(
+            "IME",
+            ella(),
+            TimelineEventType::RoomMember,
+            Some(ella().as_str()),
+            member_content_join(),
+            &["CREATE", "IJR", "IPOWER"],
+            &["IMC"],
+        );
+        let raw = to_raw_json_value(&*join).unwrap();
+
+        validate_pdu(&join, &raw, &RoomVersion::V6, &state, |id| events.get(id).cloned()).unwrap();
+    }
+
+    #[test]
+    fn unauthorized_send() {
+        let events = INITIAL_EVENTS();
+        let state = room_state(&events);
+
+        let message = to_pdu_event(
+            "MZ",
+            zara(),
+            TimelineEventType::RoomMessage,
+            None,
+            to_raw_json_value(&json!({ "msgtype": "m.text", "body": "hello" })).unwrap(),
+            &["CREATE", "IPOWER"],
+            &["IMC"],
+        );
+        let raw = to_raw_json_value(&*message).unwrap();
+
+        assert_matches!(
+            validate_pdu(&message, &raw, &RoomVersion::V6, &state, |id| events.get(id).cloned()),
+            Err(PduValidationError::Unauthorized)
+        );
+    }
+
+    #[test]
+    fn missing_auth_event() {
+        let events = INITIAL_EVENTS();
+        let state = room_state(&events);
+
+        let topic = to_pdu_event(
+            "T",
+            alice(),
+            TimelineEventType::RoomTopic,
+            Some(""),
+            to_raw_json_value(&json!({ "topic": "Hi" })).unwrap(),
+            &["CREATE", "IMA", "UNKNOWN"],
+            &["IMC"],
+        );
+        let raw = to_raw_json_value(&*topic).unwrap();
+
+        assert_matches!(
+            validate_pdu(&topic, &raw, &RoomVersion::V6, &state, |id| events.get(id).cloned()),
+            Err(PduValidationError::MissingAuthEvent(id))
+        );
+        assert_eq!(id, "$UNKNOWN:foo");
+    }
+}
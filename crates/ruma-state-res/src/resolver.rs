@@ -0,0 +1,124 @@
+//! A configurable state resolution.
+
+use std::collections::HashSet;
+
+use ruma_common::EventId;
+
+use crate::{
+    get_auth_chain_diff, get_auth_chain_diff_streaming, resolve_inner, Event, NoHooks,
+    ResolveHooks, Result, RoomVersion, StateMap,
+};
+
+/// A state resolution with custom options.
+///
+/// This is the same as [`resolve`](crate::resolve), but it can be configured:
+///
+/// * The authorization rules are given as a [`RoomVersion`], so they can differ from those of the
+///   room versions known to this crate, e.g. to experiment with a custom room version.
+/// * [`ResolveHooks`] can be set with [`Resolver::hooks`] to observe the progress of the resolution
+///   and abort it.
+/// * The auth chains can be iterated only once, with
+///   [`Resolver::resolve_with_streamed_auth_chains`].
+///
+/// ```
+/// # use ruma_state_res::{Resolver, RoomVersion};
+/// let mut room_version = RoomVersion::V11;
+/// room_version.allow_knocking = false;
+/// let resolver = Resolver::new(room_version);
+/// ```
+///
+/// ## Invariants
+///
+/// The caller must ensure that all the events are from the same room.
+pub struct Resolver<'h> {
+    room_version: RoomVersion,
+    hooks: Option<&'h mut dyn ResolveHooks>,
+}
+
+impl<'h> Resolver<'h> {
+    /// Creates a new `Resolver` with the given authorization rules.
+    pub fn new(room_version: RoomVersion) -> Self {
+        Self { room_version, hooks: None }
+    }
+
+    /// Sets the hooks called during the state resolution.
+    ///
+    /// If [`ResolveHooks::should_abort`] returns `true`, the resolution returns
+    /// [`Error::Aborted`](crate::Error::Aborted).
+    pub fn hooks(mut self, hooks: &'h mut dyn ResolveHooks) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Resolve sets of state events.
+    ///
+    /// ## Arguments
+    ///
+    /// * `state_sets` - The incoming state to resolve. Each `StateMap` represents a possible fork
+    ///   in the state of a room.
+    ///
+    /// * `auth_chain_sets` - The full recursive set of `auth_events` for each event in the
+    ///   `state_sets`.
+    ///
+    /// * `fetch_event` - Any event not found in the `event_map` will defer to this closure to find
+    ///   the event.
+    pub fn resolve<'a, E, SetIter>(
+        self,
+        state_sets: impl IntoIterator<IntoIter = SetIter>,
+        auth_chain_sets: Vec<HashSet<E::Id>>,
+        fetch_event: impl Fn(&EventId) -> Option<E>,
+    ) -> Result<StateMap<E::Id>>
+    where
+        E: Event + Clone,
+        E::Id: 'a,
+        SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+    {
+        let Self { room_version, hooks } = self;
+        resolve_inner(
+            || Ok(room_version),
+            state_sets,
+            |hooks| get_auth_chain_diff(auth_chain_sets, hooks),
+            fetch_event,
+            hooks.unwrap_or(&mut NoHooks),
+        )
+    }
+
+    /// Resolve sets of state events, with auth chains that are only iterated once.
+    ///
+    /// This is the same as [`Resolver::resolve`], except that the auth chains of the state sets
+    /// don't need to be collected in memory beforehand, which reduces memory usage for rooms with
+    /// large auth chains. Each auth chain is only iterated once, and only if there is conflicting
+    /// state, so it can be loaded lazily, e.g. from a database.
+    ///
+    /// ## Arguments
+    ///
+    /// * `state_sets` - The incoming state to resolve. Each `StateMap` represents a possible fork
+    ///   in the state of a room.
+    ///
+    /// * `auth_chains` - The full recursive set of `auth_events` for each event in the
+    ///   `state_sets`. An event ID can be yielded several times for the same auth chain.
+    ///
+    /// * `fetch_event` - Any event not found in the `event_map` will defer to this closure to find
+    ///   the event.
+    pub fn resolve_with_streamed_auth_chains<'a, E, SetIter, Chain>(
+        self,
+        state_sets: impl IntoIterator<IntoIter = SetIter>,
+        auth_chains: impl IntoIterator<Item = Chain>,
+        fetch_event: impl Fn(&EventId) -> Option<E>,
+    ) -> Result<StateMap<E::Id>>
+    where
+        E: Event + Clone,
+        E::Id: 'a,
+        SetIter: Iterator<Item = &'a StateMap<E::Id>> + Clone,
+        Chain: IntoIterator<Item = E::Id>,
+    {
+        let Self { room_version, hooks } = self;
+        resolve_inner(
+            || Ok(room_version),
+            state_sets,
+            |hooks| get_auth_chain_diff_streaming(auth_chains, hooks),
+            fetch_event,
+            hooks.unwrap_or(&mut NoHooks),
+        )
+    }
+}
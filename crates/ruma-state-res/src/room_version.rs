@@ -2,7 +2,7 @@ use ruma_common::RoomVersionId;
 
 use crate::{Error, Result};
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[allow(clippy::exhaustive_enums)]
 pub enum RoomDisposition {
     /// A room version that has a stable specification.
@@ -11,7 +11,7 @@ pub enum RoomDisposition {
     Unstable,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub enum EventFormatVersion {
     /// $id:server event id format
@@ -22,7 +22,7 @@ pub enum EventFormatVersion {
     V3,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub enum StateResolutionVersion {
     /// State resolution for rooms at version 1.
@@ -31,6 +31,7 @@ pub enum StateResolutionVersion {
     V2,
 }
 
+#[derive(Clone)]
 #[cfg_attr(not(feature = "unstable-exhaustive-types"), non_exhaustive)]
 pub struct RoomVersion {
     /// The stability of this room.
@@ -142,3 +143,22 @@ impl RoomVersion {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ruma_common::RoomVersionId;
+
+    use super::{EventFormatVersion, RoomVersion};
+
+    #[test]
+    fn event_format() {
+        let v1 = RoomVersion::new(&RoomVersionId::V1).unwrap();
+        assert_eq!(v1.event_format, EventFormatVersion::V1);
+
+        let v3 = RoomVersion::new(&RoomVersionId::V3).unwrap();
+        assert_eq!(v3.event_format, EventFormatVersion::V2);
+
+        let v11 = RoomVersion::new(&RoomVersionId::V11).unwrap();
+        assert_eq!(v11.event_format, EventFormatVersion::V3);
+    }
+}
@@ -44,6 +44,13 @@ pub trait Event {
 
     /// If this event is a redaction event this is the event it redacts.
     fn redacts(&self) -> Option<&Self::Id>;
+
+    /// The depth of this event in the room graph, if it is known.
+    ///
+    /// This is not used by state resolution. The default implementation returns `None`.
+    fn depth(&self) -> Option<u64> {
+        None
+    }
 }
 
 impl<T: Event> Event for &T {
@@ -88,6 +95,10 @@ impl<T: Event> Event for &T {
     fn redacts(&self) -> Option<&Self::Id> {
         (*self).redacts()
     }
+
+    fn depth(&self) -> Option<u64> {
+        (*self).depth()
+    }
 }
 
 impl<T: Event> Event for Arc<T> {
@@ -132,4 +143,8 @@ impl<T: Event> Event for Arc<T> {
     fn redacts(&self) -> Option<&Self::Id> {
         (**self).redacts()
     }
+
+    fn depth(&self) -> Option<u64> {
+        (**self).depth()
+    }
 }
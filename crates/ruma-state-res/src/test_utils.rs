@@ -640,6 +640,15 @@ pub(crate) mod event {
                 _ => unreachable!("new PDU version"),
             }
         }
+
+        fn depth(&self) -> Option<u64> {
+            match &self.rest {
+                Pdu::RoomV1Pdu(ev) => Some(ev.depth.into()),
+                Pdu::RoomV3Pdu(ev) => Some(ev.depth.into()),
+                #[allow(unreachable_patterns)]
+                _ => unreachable!("new PDU version"),
+            }
+        }
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]